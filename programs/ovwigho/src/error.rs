@@ -10,4 +10,132 @@ pub enum ErrorCode {
     AssetAlreadyInitialized,
     #[msg("Not enough cNFTs burned")]
     NotEnoughBurns,
+    #[msg("The NFT collection has reached its max supply.")]
+    MaxSupplyReached,
+    #[msg("The collection does not belong to this config.")]
+    InvalidCollection,
+    #[msg("Creator shares must add up to 100.")]
+    InvalidCreatorShares,
+    #[msg("Too many creators.")]
+    TooManyCreators,
+    #[msg("The config must be listed as a creator.")]
+    ConfigCreatorMissing,
+    #[msg("The cNFT metadata is immutable.")]
+    MetadataImmutable,
+    #[msg("Moderation is not enabled for the NFT collection.")]
+    ModerationDisabled,
+    #[msg("Moderation is already enabled for the NFT collection.")]
+    ModerationAlreadyEnabled,
+    #[msg("The asset was not crafted by this player.")]
+    AssetPlayerMismatch,
+    #[msg("Basis points must not exceed 10000.")]
+    InvalidBasisPoints,
+    #[msg("A craft must cost at least one cNFT.")]
+    InvalidCraftCost,
+    #[msg("Decomposing this NFT would not return any cNFTs.")]
+    NothingToDecompose,
+    #[msg("The maximum number of tiers has been registered.")]
+    TooManyTiers,
+    #[msg("A merge must consume at least two NFTs.")]
+    InvalidMergeCost,
+    #[msg("The number of NFTs to merge does not match the tier's merge cost.")]
+    InvalidMergeCount,
+    #[msg("The source tier does not precede the target tier.")]
+    InvalidSourceTier,
+    #[msg("The level URI prefix is too long.")]
+    UriPrefixTooLong,
+    #[msg("Leveling up is not enabled for this payment.")]
+    LevelUpDisabled,
+    #[msg("Not enough crafting points.")]
+    NotEnoughPoints,
+    #[msg("The asset is not owned by this player.")]
+    AssetOwnerMismatch,
+    #[msg("The asset must sign or be the craft's derived address.")]
+    InvalidAssetAddress,
+    #[msg("Too many revenue shares.")]
+    TooManyRevenueShares,
+    #[msg("Revenue shares must add up to 10000 basis points.")]
+    InvalidRevenueShares,
+    #[msg("The recipient does not match the configured revenue share.")]
+    RevenueShareRecipientMismatch,
+    #[msg("The treasury does not hold enough funds.")]
+    InsufficientTreasuryBalance,
+    #[msg("Token payment accounts are missing.")]
+    PaymentAccountsMissing,
+    #[msg("The payment mint does not match the config.")]
+    PaymentMintMismatch,
+    #[msg("The treasury token account is not the config's associated token account.")]
+    InvalidTreasuryTokenAccount,
+    #[msg("Too many sponsors.")]
+    TooManySponsors,
+    #[msg("The payer is not an allowed sponsor.")]
+    SponsorNotAllowed,
+    #[msg("The signer is neither the player nor an active session for this instruction.")]
+    UnauthorizedSigner,
+    #[msg("Invalid session expiry.")]
+    InvalidSessionExpiry,
+    #[msg("Invalid session scope.")]
+    InvalidSessionScope,
+    #[msg("Too many cranks.")]
+    TooManyCranks,
+    #[msg("The player has not opted in to auto crafting.")]
+    AutoCraftDisabled,
+    #[msg("The signer is neither the player nor an approved crank.")]
+    CrankNotAllowed,
+    #[msg("Too many contributors.")]
+    TooManyContributors,
+    #[msg("The beneficiary does not accept contributions from this player.")]
+    ContributionNotAllowed,
+    #[msg("A contribution account is required when burning for another player.")]
+    ContributionAccountMissing,
+    #[msg("Too many guild members.")]
+    TooManyGuildMembers,
+    #[msg("The player is not a member of this guild.")]
+    NotGuildMember,
+    #[msg("The recipient must be one of the guild's members.")]
+    InvalidGuildRecipient,
+    #[msg("The season must end after it starts.")]
+    InvalidSeasonWindow,
+    #[msg("The active season account is required.")]
+    SeasonAccountMissing,
+    #[msg("The season is not active.")]
+    SeasonNotActive,
+    #[msg("The drop must end after it starts.")]
+    InvalidDropWindow,
+    #[msg("cNFTs can only be minted through a drop.")]
+    DropRequired,
+    #[msg("The drop is not open.")]
+    DropNotOpen,
+    #[msg("The drop is sold out.")]
+    DropSoldOut,
+    #[msg("An allowlist proof and claim account are required for this drop.")]
+    AllowlistProofMissing,
+    #[msg("The wallet is not on the drop's allowlist.")]
+    NotOnAllowlist,
+    #[msg("The wallet has claimed its full allowance.")]
+    AllowanceExceeded,
+    #[msg("A gate account is required.")]
+    GateAccountMissing,
+    #[msg("The player does not meet the gate requirement.")]
+    GateNotMet,
+    #[msg("Template name, symbol or uri is too long.")]
+    TemplateFieldTooLong,
+    #[msg("The template does not belong to this config.")]
+    InvalidTemplate,
+    #[msg("Airdrop accounts must be [recipient, template] pairs matching the recipients and template ids.")]
+    InvalidAirdropAccounts,
+    #[msg("The config account is not a legacy config.")]
+    InvalidLegacyConfig,
+    #[msg("Arithmetic overflow.")]
+    MathOverflow,
+    #[msg("A contribution account is only used when burning for another player.")]
+    UnexpectedContributionAccount,
+    #[msg("New guild members must sign to join.")]
+    MemberConsentMissing,
+    #[msg("A player progress account is required unless burning into a guild.")]
+    PlayerProgressAccountMissing,
+    #[msg("Guild burns do not take a player progress account.")]
+    UnexpectedPlayerProgressAccount,
+    #[msg("An allowlist claim is only used when minting through a drop.")]
+    UnexpectedAllowlistClaim,
 }
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct NftBurned {
+    pub player: Pubkey,
+    pub asset: Pubkey,
+    pub crafting_points: u32,
+}
+
+#[event]
+pub struct NftDecomposed {
+    pub player: Pubkey,
+    pub asset: Pubkey,
+    pub cnfts_minted: u32,
+}
+
+#[event]
+pub struct NftsMerged {
+    pub player: Pubkey,
+    pub asset: Pubkey,
+    pub tier: u8,
+    pub nfts_burned: u8,
+}
+
+#[event]
+pub struct NftLeveledUp {
+    pub player: Pubkey,
+    pub asset: Pubkey,
+    pub level: u32,
+    pub xp: u32,
+}
+
+#[event]
+pub struct CnftBurnContributed {
+    pub contributor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub asset: Pubkey,
+    pub cnfts_burned: u32,
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode, CnftTemplate, Config, MAX_TEMPLATE_NAME_LEN, MAX_TEMPLATE_SYMBOL_LEN,
+    MAX_TEMPLATE_URI_LEN,
+};
+
+#[derive(Accounts)]
+pub struct AddCnftTemplate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            b"template",
+            config.key().as_ref(),
+            &(config.template_count + 1).to_le_bytes(),
+        ],
+        bump,
+        space = CnftTemplate::DISCRIMINATOR.len() + CnftTemplate::INIT_SPACE,
+    )]
+    pub template: Box<Account<'info, CnftTemplate>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddCnftTemplate<'info> {
+    pub fn add_template(
+        &mut self,
+        name: String,
+        symbol: String,
+        uri: String,
+        bumps: &AddCnftTemplateBumps,
+    ) -> Result<()> {
+        require!(
+            name.len() <= MAX_TEMPLATE_NAME_LEN
+                && symbol.len() <= MAX_TEMPLATE_SYMBOL_LEN
+                && uri.len() <= MAX_TEMPLATE_URI_LEN,
+            ErrorCode::TemplateFieldTooLong
+        );
+
+        self.config.template_count += 1;
+
+        self.template.set_inner(CnftTemplate {
+            config: self.config.key(),
+            id: self.config.template_count,
+            name,
+            symbol,
+            uri,
+            bump: bumps.template,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<AddCnftTemplate>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    ctx.accounts.add_template(name, symbol, uri, &ctx.bumps)?;
+
+    Ok(())
+}
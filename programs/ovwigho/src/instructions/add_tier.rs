@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::CreateCollectionV2CpiBuilder,
+    types::{MasterEdition, Plugin, PluginAuthorityPair},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{error::ErrorCode, Config, Tier, MAX_TIERS};
+
+#[derive(Accounts)]
+pub struct AddTier<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+        constraint = (config.tier_count as usize) < MAX_TIERS @ ErrorCode::TooManyTiers,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"tier", config.key().as_ref(), &[config.tier_count + 1]],
+        bump,
+        space = Tier::DISCRIMINATOR.len() + Tier::INIT_SPACE,
+    )]
+    pub tier: Box<Account<'info, Tier>>,
+
+    #[account(
+        mut,
+        constraint = collection.data_is_empty() @ ErrorCode::CollectionAlreadyInitialized
+    )]
+    pub collection: Signer<'info>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddTier<'info> {
+    pub fn add_tier(
+        &mut self,
+        merge_cost: u8,
+        max_supply: Option<u32>,
+        bumps: &AddTierBumps,
+    ) -> Result<()> {
+        require!(merge_cost >= 2, ErrorCode::InvalidMergeCost);
+
+        self.config.tier_count += 1;
+
+        self.tier.set_inner(Tier {
+            config: self.config.key(),
+            index: self.config.tier_count,
+            collection: self.collection.key(),
+            merge_cost,
+            max_supply,
+            total_minted: 0,
+            total_burned: 0,
+            bump: bumps.tier,
+        });
+
+        Ok(())
+    }
+
+    pub fn create_collection(&mut self, name: String, uri: String) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        CreateCollectionV2CpiBuilder::new(&self.core_program.to_account_info())
+            .collection(&self.collection.to_account_info())
+            .update_authority(Some(&self.config.to_account_info()))
+            .payer(&self.authority.to_account_info())
+            .system_program(&self.system_program.to_account_info())
+            .name(name)
+            .uri(uri)
+            .plugins(vec![PluginAuthorityPair {
+                plugin: Plugin::MasterEdition(MasterEdition {
+                    max_supply: self.tier.max_supply,
+                    name: None,
+                    uri: None,
+                }),
+                authority: None,
+            }])
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<AddTier>,
+    merge_cost: u8,
+    max_supply: Option<u32>,
+    name: String,
+    uri: String,
+) -> Result<()> {
+    ctx.accounts.add_tier(merge_cost, max_supply, &ctx.bumps)?;
+    ctx.accounts.create_collection(name, uri)?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use mpl_core::{instructions::BurnV1CpiBuilder, ID as CORE_PROGRAM_ID};
+
+use crate::{
+    error::ErrorCode,
+    utils::{asset_attribute, asset_is_frozen, record_freeze},
+    Config, PlayerProgress,
+};
+
+#[derive(Accounts)]
+pub struct AdminBurnNFT<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+        constraint = config.moderation @ ErrorCode::ModerationDisabled,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub player: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player.key().as_ref()],
+        bump = player_progress.bump,
+    )]
+    pub player_progress: Box<Account<'info, PlayerProgress>>,
+
+    /// CHECK: Collection Account that will be checked by core
+    #[account(
+        mut,
+        constraint = !nft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.nft_collection,
+    )]
+    pub nft_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Asset Account that will be checked by core
+    #[account(
+        mut,
+        constraint = asset_attribute(&asset, "Player") == Some(player.key().to_string())
+            @ ErrorCode::AssetPlayerMismatch,
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AdminBurnNFT<'info> {
+    pub fn burn_nft(&mut self) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        BurnV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.nft_collection.to_account_info()))
+            .payer(&self.authority.to_account_info())
+            .authority(Some(&self.config.to_account_info()))
+            .system_program(Some(&self.system_program.to_account_info()))
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    /// Minted totals are left alone so edition numbers and max supply keep
+    /// counting every NFT ever crafted.
+    pub fn record_burn(&mut self, was_frozen: bool) -> Result<()> {
+        self.config.total_nfts_burned += 1;
+
+        if was_frozen {
+            record_freeze(&mut self.config, &mut self.player_progress, false);
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<AdminBurnNFT>) -> Result<()> {
+    let was_frozen = asset_is_frozen(&ctx.accounts.asset);
+    ctx.accounts.burn_nft()?;
+
+    ctx.accounts.record_burn(was_frozen)?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::UpdatePluginV1CpiBuilder,
+    types::{PermanentFreezeDelegate, Plugin},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    error::ErrorCode,
+    utils::{asset_attribute, asset_is_frozen, record_freeze},
+    Config, PlayerProgress,
+};
+
+#[derive(Accounts)]
+pub struct AdminFreezeNFT<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+        constraint = config.moderation @ ErrorCode::ModerationDisabled,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub player: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player.key().as_ref()],
+        bump = player_progress.bump,
+    )]
+    pub player_progress: Box<Account<'info, PlayerProgress>>,
+
+    /// CHECK: Collection Account that will be checked by core
+    #[account(
+        mut,
+        constraint = !nft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.nft_collection,
+    )]
+    pub nft_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Asset Account that will be checked by core
+    #[account(
+        mut,
+        constraint = asset_attribute(&asset, "Player") == Some(player.key().to_string())
+            @ ErrorCode::AssetPlayerMismatch,
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AdminFreezeNFT<'info> {
+    pub fn freeze_nft(&mut self, frozen: bool) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.nft_collection.to_account_info()))
+            .payer(&self.authority.to_account_info())
+            .authority(Some(&self.config.to_account_info()))
+            .system_program(&self.system_program.to_account_info())
+            .plugin(Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
+                frozen,
+            }))
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    pub fn record_freeze(&mut self, frozen: bool) -> Result<()> {
+        record_freeze(&mut self.config, &mut self.player_progress, frozen);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<AdminFreezeNFT>, frozen: bool) -> Result<()> {
+    if asset_is_frozen(&ctx.accounts.asset) == frozen {
+        return Ok(());
+    }
+
+    ctx.accounts.freeze_nft(frozen)?;
+
+    ctx.accounts.record_freeze(frozen)?;
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use mpl_account_compression::ID as MPL_ACCOUNT_COMPRESSION_ID;
+use mpl_bubblegum::ID as BUBBLEGUM_ID;
+use mpl_noop::ID as MPL_NOOP_ID;
+
+use crate::{
+    error::ErrorCode,
+    utils::{active_season, mint_cnft_leaf, CnftMintAccounts},
+    CnftTemplate, Config, Season,
+};
+
+/// Mints one cNFT of `template_ids[i]` to `recipients[i]`, with each
+/// `[recipient, template]` pair also passed as remaining accounts. Batch size
+/// is bounded by the transaction's compute budget.
+#[derive(Accounts)]
+pub struct AirdropcNFTs<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    /// CHECK: Collection Account that will be checked by the Bubblegum Program
+    #[account(
+        mut,
+        constraint = !cnft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.cnft_collection,
+    )]
+    pub cnft_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Tree Config checks will be performed by the Bubblegum Program
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: Merkle Tree account that will be checked by the Bubblegum Program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: MPL Core CPI Signer account that will be checked by the Bubblegum Program
+    pub mpl_core_cpi_signer: UncheckedAccount<'info>,
+    /// CHECK: MPL NOOP Program checked by the corresponding address
+    #[account(address = MPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: Bubblegum Program checked by the corresponding address
+    #[account(address = BUBBLEGUM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: MPL Account Compression Program checked by the corresponding address
+    #[account(address = MPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This is the ID of the Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> AirdropcNFTs<'info> {
+    pub fn check_season(&self) -> Result<()> {
+        active_season(&self.config, self.season.as_deref())?;
+
+        Ok(())
+    }
+
+    pub fn airdrop(
+        &mut self,
+        recipients: &[Pubkey],
+        template_ids: &[u32],
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<u32> {
+        let account_keys: Vec<Pubkey> = remaining_accounts
+            .iter()
+            .map(|account| account.key())
+            .collect();
+        check_airdrop_accounts(&self.config.key(), recipients, template_ids, &account_keys)?;
+
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let bubblegum_program = &self.bubblegum_program.to_account_info();
+        let tree_config = &self.tree_config.to_account_info();
+        let merkle_tree = &self.merkle_tree.to_account_info();
+        let payer = &self.authority.to_account_info();
+        let config = &self.config.to_account_info();
+        let cnft_collection = &self.cnft_collection.to_account_info();
+        let mpl_core_cpi_signer = &self.mpl_core_cpi_signer.to_account_info();
+        let log_wrapper = &self.log_wrapper.to_account_info();
+        let compression_program = &self.compression_program.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let mpl_core_program = &self.mpl_core_program.to_account_info();
+
+        for (pair, template_id) in remaining_accounts.chunks_exact(2).zip(template_ids) {
+            let (recipient, template) = (&pair[0], &pair[1]);
+
+            let template = Account::<CnftTemplate>::try_from(template)?;
+            check_airdrop_template(&self.config.key(), *template_id, &template)?;
+
+            mint_cnft_leaf(
+                &CnftMintAccounts {
+                    bubblegum_program,
+                    tree_config,
+                    leaf_owner: recipient,
+                    leaf_delegate: recipient,
+                    merkle_tree,
+                    payer,
+                    config,
+                    cnft_collection,
+                    mpl_core_cpi_signer,
+                    log_wrapper,
+                    compression_program,
+                    system_program,
+                    mpl_core_program,
+                },
+                self.config.leaf_metadata(
+                    self.config.key(),
+                    template.name.clone(),
+                    template.symbol.clone(),
+                    template.uri.clone(),
+                ),
+                signer_seeds,
+            )?;
+        }
+
+        Ok(recipients.len() as u32)
+    }
+
+    pub fn record_airdrop(&mut self, cnfts_minted: u32) -> Result<()> {
+        self.config.total_cnfts_minted = self
+            .config
+            .total_cnfts_minted
+            .checked_add(cnfts_minted)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// Checks the remaining accounts are the `[recipient, template]` pairs named by
+/// the args, with each template at its PDA under `config`.
+pub fn check_airdrop_accounts(
+    config: &Pubkey,
+    recipients: &[Pubkey],
+    template_ids: &[u32],
+    account_keys: &[Pubkey],
+) -> Result<()> {
+    require!(
+        !recipients.is_empty()
+            && recipients.len() == template_ids.len()
+            && account_keys.len() == recipients.len() * 2,
+        ErrorCode::InvalidAirdropAccounts
+    );
+
+    for ((pair, recipient), template_id) in account_keys
+        .chunks_exact(2)
+        .zip(recipients)
+        .zip(template_ids)
+    {
+        require_keys_eq!(pair[0], *recipient, ErrorCode::InvalidAirdropAccounts);
+        require_keys_eq!(
+            pair[1],
+            CnftTemplate::address(config, *template_id),
+            ErrorCode::InvalidTemplate
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks a loaded template is the one its PDA was derived for.
+pub fn check_airdrop_template(
+    config: &Pubkey,
+    template_id: u32,
+    template: &CnftTemplate,
+) -> Result<()> {
+    require_keys_eq!(template.config, *config, ErrorCode::InvalidTemplate);
+    require_eq!(template.id, template_id, ErrorCode::InvalidTemplate);
+
+    Ok(())
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AirdropcNFTs<'info>>,
+    recipients: Vec<Pubkey>,
+    template_ids: Vec<u32>,
+) -> Result<()> {
+    ctx.accounts.check_season()?;
+    let cnfts_minted = ctx
+        .accounts
+        .airdrop(&recipients, &template_ids, ctx.remaining_accounts)?;
+    ctx.accounts.record_airdrop(cnfts_minted)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn airdrop_accounts_must_match_the_args() {
+        let config = Pubkey::new_unique();
+        let recipients = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let template_ids = [1, 7];
+        let accounts = [
+            recipients[0],
+            CnftTemplate::address(&config, 1),
+            recipients[1],
+            CnftTemplate::address(&config, 7),
+        ];
+
+        assert!(check_airdrop_accounts(&config, &recipients, &template_ids, &accounts).is_ok());
+
+        let invalid_accounts: Error = ErrorCode::InvalidAirdropAccounts.into();
+        assert_eq!(
+            check_airdrop_accounts(&config, &[], &[], &[]).unwrap_err(),
+            invalid_accounts
+        );
+        assert_eq!(
+            check_airdrop_accounts(&config, &recipients, &[1], &accounts).unwrap_err(),
+            invalid_accounts
+        );
+        assert_eq!(
+            check_airdrop_accounts(&config, &recipients, &template_ids, &accounts[..2])
+                .unwrap_err(),
+            invalid_accounts
+        );
+
+        let mut swapped = accounts;
+        swapped.swap(0, 2);
+        assert_eq!(
+            check_airdrop_accounts(&config, &recipients, &template_ids, &swapped).unwrap_err(),
+            invalid_accounts
+        );
+
+        let other_config = [
+            recipients[0],
+            CnftTemplate::address(&Pubkey::new_unique(), 1),
+            recipients[1],
+            CnftTemplate::address(&config, 7),
+        ];
+        assert_eq!(
+            check_airdrop_accounts(&config, &recipients, &template_ids, &other_config).unwrap_err(),
+            ErrorCode::InvalidTemplate.into()
+        );
+        assert_eq!(
+            check_airdrop_accounts(&config, &recipients, &[7, 1], &accounts).unwrap_err(),
+            ErrorCode::InvalidTemplate.into()
+        );
+    }
+
+    #[test]
+    fn airdrop_templates_must_belong_to_the_config() {
+        let config = Pubkey::new_unique();
+        let mut template = CnftTemplate {
+            config,
+            id: 1,
+            name: "Shard".into(),
+            symbol: "SHRD".into(),
+            uri: "https://example.com/shard.json".into(),
+            bump: 255,
+        };
+
+        assert!(check_airdrop_template(&config, 1, &template).is_ok());
+        assert_eq!(
+            check_airdrop_template(&config, 2, &template).unwrap_err(),
+            ErrorCode::InvalidTemplate.into()
+        );
+
+        template.config = Pubkey::new_unique();
+        assert_eq!(
+            check_airdrop_template(&config, 1, &template).unwrap_err(),
+            ErrorCode::InvalidTemplate.into()
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use mpl_account_compression::ID as MPL_ACCOUNT_COMPRESSION_ID;
+use mpl_bubblegum::instructions::BurnV2CpiBuilder;
+use mpl_bubblegum::utils::get_asset_id;
+use mpl_bubblegum::ID as BUBBLEGUM_ID;
+use mpl_noop::ID as MPL_NOOP_ID;
+
+use crate::{
+    error::ErrorCode,
+    utils::{active_season, leaf_creator_hash},
+    Config, MintDrop, PlayerProgress, Season,
+};
+
+/// Burns a cNFT that was minted with the `config` PDA as leaf delegate, on
+/// behalf of a player who opted in to auto crafting.
+#[derive(Accounts)]
+pub struct AutoBurncNFT<'info> {
+    /// The player or an approved crank
+    #[account(
+        mut,
+        constraint = cranker.key() == player.key()
+            || config.cranks.contains(&cranker.key()) @ ErrorCode::CrankNotAllowed,
+    )]
+    pub cranker: Signer<'info>,
+
+    pub player: SystemAccount<'info>,
+
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    /// The drop the leaf was minted through, whose stamp is part of the leaf's
+    /// creator hash
+    #[account(
+        seeds = [b"drop", config.key().as_ref(), &mint_drop.id.to_le_bytes()],
+        bump = mint_drop.bump,
+    )]
+    pub mint_drop: Option<Box<Account<'info, MintDrop>>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player.key().as_ref()],
+        bump = player_progress.bump,
+        constraint = player_progress.auto_craft @ ErrorCode::AutoCraftDisabled,
+    )]
+    pub player_progress: Box<Account<'info, PlayerProgress>>,
+
+    /// CHECK: Collection Account that will be checked by the Bubblegum Program
+    #[account(
+        mut,
+        constraint = !cnft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.cnft_collection,
+    )]
+    pub cnft_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Tree Config checks will be performed by the Bubblegum Program
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: Merkle Tree account that will be checked by the Bubblegum Program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: MPL Core CPI Signer account that will be checked by the Bubblegum Program
+    pub mpl_core_cpi_signer: UncheckedAccount<'info>,
+    /// CHECK: MPL NOOP Program checked by the corresponding address
+    #[account(address = MPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: Bubblegum Program checked by the corresponding address
+    #[account(address = BUBBLEGUM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: MPL Account Compression Program checked by the corresponding address
+    #[account(address = MPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This is the ID of the Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> AutoBurncNFT<'info> {
+    pub fn check_season(&mut self) -> Result<()> {
+        let season = active_season(&self.config, self.season.as_deref())?;
+        self.player_progress.sync_season(season);
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn burn_cnft(
+        &self,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+        asset_data_hash: [u8; 32],
+        flags: u8,
+        creator_hash: Option<[u8; 32]>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let bubblegum_program = &self.bubblegum_program.to_account_info();
+        let tree_config = &self.tree_config.to_account_info();
+        let merkle_tree = &self.merkle_tree.to_account_info();
+        let log_wrapper = &self.log_wrapper.to_account_info();
+        let compression_program = &self.compression_program.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let cranker = &self.cranker.to_account_info();
+        let player = &self.player.to_account_info();
+        let config = &self.config.to_account_info();
+        let cnft_collection = &self.cnft_collection.to_account_info();
+        let mpl_core_cpi_signer = &self.mpl_core_cpi_signer.to_account_info();
+        let mpl_core_program = &self.mpl_core_program.to_account_info();
+
+        // Leaves minted under an earlier creator set, such as before a creator
+        // update or a config migration, pass the creator hash they were minted with.
+        let creator_hash = match creator_hash {
+            Some(creator_hash) => creator_hash,
+            None => leaf_creator_hash(
+                &self.config,
+                self.config.key(),
+                self.mint_drop.as_deref().map(|drop| (drop.key(), &**drop)),
+            )?,
+        };
+
+        let mut builder = BurnV2CpiBuilder::new(bubblegum_program);
+
+        builder
+            .tree_config(tree_config)
+            .payer(cranker)
+            .authority(Some(config))
+            .leaf_owner(player)
+            .leaf_delegate(Some(config))
+            .merkle_tree(merkle_tree)
+            .core_collection(Some(cnft_collection))
+            .mpl_core_cpi_signer(Some(mpl_core_cpi_signer))
+            .log_wrapper(log_wrapper)
+            .compression_program(compression_program)
+            .mpl_core_program(mpl_core_program)
+            .system_program(system_program)
+            .root(root)
+            .data_hash(data_hash)
+            .creator_hash(creator_hash)
+            .asset_data_hash(asset_data_hash)
+            .flags(flags)
+            .nonce(nonce)
+            .index(index);
+
+        for account in remaining_accounts {
+            builder.add_remaining_accounts(&[(account, false, false)]);
+        }
+
+        builder.invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    pub fn record_burn(&mut self, nonce: u64) -> Result<()> {
+        let asset_id = get_asset_id(&self.merkle_tree.key(), nonce);
+
+        self.player_progress.record_cnft_burn(asset_id);
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AutoBurncNFT<'info>>,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    asset_data_hash: [u8; 32],
+    flags: u8,
+    creator_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts.check_season()?;
+    ctx.accounts.burn_cnft(
+        root,
+        data_hash,
+        nonce,
+        index,
+        asset_data_hash,
+        flags,
+        creator_hash,
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.record_burn(nonce)?;
+
+    Ok(())
+}
@@ -1,15 +1,36 @@
 use anchor_lang::prelude::*;
 use mpl_account_compression::ID as MPL_ACCOUNT_COMPRESSION_ID;
 use mpl_bubblegum::instructions::BurnV2CpiBuilder;
+use mpl_bubblegum::utils::get_asset_id;
 use mpl_bubblegum::ID as BUBBLEGUM_ID;
 use mpl_noop::ID as MPL_NOOP_ID;
 
-use crate::{error::ErrorCode, Config, PlayerProgress};
+use crate::{
+    error::ErrorCode,
+    events::CnftBurnContributed,
+    utils::{active_season, authorize_player, leaf_creator_hash},
+    Config, Contribution, Guild, MintDrop, PlayerProgress, Season, SessionToken,
+    SESSION_SCOPE_BURN_CNFT,
+};
 
 #[derive(Accounts)]
 pub struct BurncNFT<'info> {
-    #[account(mut)]
-    pub player: Signer<'info>,
+    pub player: SystemAccount<'info>,
+
+    /// The player, or a session key holding a `SessionToken` from the player
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"session", player.key().as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Box<Account<'info, SessionToken>>>,
+
+    #[account(
+        mut,
+        constraint = config.can_pay_for(&payer.key(), &signer.key()) @ ErrorCode::SponsorNotAllowed,
+    )]
+    pub payer: Signer<'info>,
 
     pub authority: SystemAccount<'info>,
 
@@ -20,15 +41,56 @@ pub struct BurncNFT<'info> {
     )]
     pub config: Box<Account<'info, Config>>,
 
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    /// The drop the leaf was minted through, whose stamp is part of the leaf's
+    /// creator hash
+    #[account(
+        seeds = [b"drop", config.key().as_ref(), &mint_drop.id.to_le_bytes()],
+        bump = mint_drop.bump,
+    )]
+    pub mint_drop: Option<Box<Account<'info, MintDrop>>>,
+
+    /// The player credited with the burn; the burner themselves unless contributing
+    /// to a teammate's craft
+    pub beneficiary: SystemAccount<'info>,
+
     #[account(
         init_if_needed,
-        payer = player,
-        seeds = [b"player", player.key().as_ref()],
+        payer = payer,
+        seeds = [b"player", beneficiary.key().as_ref()],
         bump,
         space = PlayerProgress::DISCRIMINATOR.len() + PlayerProgress::INIT_SPACE,
+    )]
+    /// Left out of guild burns, which credit the guild's pool instead
+    pub player_progress: Option<Box<Account<'info, PlayerProgress>>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"contribution", beneficiary.key().as_ref(), player.key().as_ref()],
+        bump,
+        space = Contribution::DISCRIMINATOR.len() + Contribution::INIT_SPACE,
+        constraint = beneficiary.key() != player.key() @ ErrorCode::UnexpectedContributionAccount,
+    )]
+    /// Only passed when burning for another player, so self-burns pay no rent for it
+    pub contribution: Option<Box<Account<'info, Contribution>>>,
 
+    /// Routes the burn into this guild's pool instead of a player's progress
+    #[account(
+        mut,
+        has_one = config,
+        constraint = guild.members.contains(&player.key()) @ ErrorCode::NotGuildMember,
     )]
-    pub player_progress: Box<Account<'info, PlayerProgress>>,
+    pub guild: Option<Box<Account<'info, Guild>>>,
+
+    /// CHECK: Current delegate of the leaf, checked by the Bubblegum Program. This is the
+    /// player unless the leaf was delegated to the config, as session mints are
+    pub leaf_delegate: UncheckedAccount<'info>,
 
     /// CHECK: Collection Account that will be checked by the Bubblegum Program
     #[account(
@@ -62,34 +124,65 @@ pub struct BurncNFT<'info> {
 }
 
 impl<'info> BurncNFT<'info> {
+    pub fn check_season(&mut self) -> Result<()> {
+        let season = active_season(&self.config, self.season.as_deref())?;
+        if let Some(player_progress) = self.player_progress.as_deref_mut() {
+            player_progress.sync_season(season);
+        }
+
+        Ok(())
+    }
+
+    pub fn authorize(&self) -> Result<()> {
+        authorize_player(
+            &self.player.key(),
+            &self.signer.key(),
+            self.session_token.as_deref(),
+            SESSION_SCOPE_BURN_CNFT,
+        )
+    }
+
     pub fn init_player_progress(&mut self, bumps: &BurncNFTBumps) -> Result<()> {
-        if self.player_progress.bump == 0 {
-            self.player_progress.set_inner(PlayerProgress {
-                player: self.player.key(),
+        let Some(player_progress) = self.player_progress.as_deref_mut() else {
+            require!(
+                self.guild.is_some(),
+                ErrorCode::PlayerProgressAccountMissing
+            );
+            return Ok(());
+        };
+
+        require!(
+            self.guild.is_none(),
+            ErrorCode::UnexpectedPlayerProgressAccount
+        );
+
+        if player_progress.bump == 0 {
+            player_progress.set_inner(PlayerProgress {
+                player: self.beneficiary.key(),
                 authority: self.authority.key(),
-                total_cnfts_burned: 0,
-                total_nfts_minted: 0,
-                bump: bumps.player_progress,
+                bump: bumps.player_progress.unwrap_or_default(),
+                ..Default::default()
             });
         }
 
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn burn_cnft(
         &self,
         root: [u8; 32],
         data_hash: [u8; 32],
-        creator_hash: [u8; 32],
         nonce: u64,
         index: u32,
         asset_data_hash: [u8; 32],
         flags: u8,
+        creator_hash: Option<[u8; 32]>,
         remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         let seeds = &[
             &b"config"[..],
-            &self.authority.key.as_ref(),
+            self.authority.key.as_ref(),
             &[self.config.bump],
         ];
 
@@ -101,20 +194,40 @@ impl<'info> BurncNFT<'info> {
         let log_wrapper = &self.log_wrapper.to_account_info();
         let compression_program = &self.compression_program.to_account_info();
         let system_program = &self.system_program.to_account_info();
-        let authority = &self.config.to_account_info();
-        let player = &self.player;
+        let player = &self.player.to_account_info();
+        let config = &self.config.to_account_info();
+        // A session has been authorized in `authorize`, and burns through the
+        // config PDA the leaf was delegated to when it was minted.
+        let burn_authority = if self.signer.key() == self.player.key() {
+            &self.signer.to_account_info()
+        } else {
+            config
+        };
+        let leaf_delegate = &self.leaf_delegate.to_account_info();
+        let payer = &self.payer.to_account_info();
         let cnft_collection = &self.cnft_collection.to_account_info();
         let mpl_core_cpi_signer = &self.mpl_core_cpi_signer.to_account_info();
         let mpl_core_program = &self.mpl_core_program.to_account_info();
 
+        // Leaves minted under an earlier creator set, such as before a creator
+        // update or a config migration, pass the creator hash they were minted with.
+        let creator_hash = match creator_hash {
+            Some(creator_hash) => creator_hash,
+            None => leaf_creator_hash(
+                &self.config,
+                self.config.key(),
+                self.mint_drop.as_deref().map(|drop| (drop.key(), &**drop)),
+            )?,
+        };
+
         let mut builder = BurnV2CpiBuilder::new(bubblegum_program);
 
         builder
             .tree_config(tree_config)
-            .payer(player)
-            .authority(Some(player))
+            .payer(payer)
+            .authority(Some(burn_authority))
             .leaf_owner(player)
-            .leaf_delegate(Some(player))
+            .leaf_delegate(Some(leaf_delegate))
             .merkle_tree(merkle_tree)
             .core_collection(Some(cnft_collection))
             .mpl_core_cpi_signer(Some(mpl_core_cpi_signer))
@@ -139,36 +252,79 @@ impl<'info> BurncNFT<'info> {
         Ok(())
     }
 
-    pub fn record_burn(&mut self) -> Result<()> {
-        self.player_progress.total_cnfts_burned += 1;
+    pub fn record_burn(&mut self, nonce: u64, bumps: &BurncNFTBumps) -> Result<()> {
+        let asset_id = get_asset_id(&self.merkle_tree.key(), nonce);
+
+        if let Some(guild) = self.guild.as_deref_mut() {
+            guild.record_cnft_burn(asset_id);
+            return Ok(());
+        }
+
+        let Some(player_progress) = self.player_progress.as_deref_mut() else {
+            return err!(ErrorCode::PlayerProgressAccountMissing);
+        };
+        player_progress.record_cnft_burn(asset_id);
+
+        if self.beneficiary.key() == self.player.key() {
+            return Ok(());
+        }
+
+        require!(
+            player_progress.accepts_contribution_from(&self.player.key()),
+            ErrorCode::ContributionNotAllowed
+        );
+
+        let Some(contribution) = self.contribution.as_deref_mut() else {
+            return err!(ErrorCode::ContributionAccountMissing);
+        };
+
+        if contribution.bump == 0 {
+            contribution.set_inner(Contribution {
+                beneficiary: self.beneficiary.key(),
+                contributor: self.player.key(),
+                cnfts_burned: 0,
+                bump: bumps.contribution.unwrap_or_default(),
+            });
+        }
+        contribution.cnfts_burned += 1;
+
+        emit!(CnftBurnContributed {
+            contributor: self.player.key(),
+            beneficiary: self.beneficiary.key(),
+            asset: asset_id,
+            cnfts_burned: contribution.cnfts_burned,
+        });
 
         Ok(())
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler<'info>(
     ctx: Context<'_, '_, 'info, 'info, BurncNFT<'info>>,
     root: [u8; 32],
     data_hash: [u8; 32],
-    creator_hash: [u8; 32],
     nonce: u64,
     index: u32,
     asset_data_hash: [u8; 32],
     flags: u8,
+    creator_hash: Option<[u8; 32]>,
 ) -> Result<()> {
+    ctx.accounts.authorize()?;
     ctx.accounts.init_player_progress(&ctx.bumps)?;
+    ctx.accounts.check_season()?;
     ctx.accounts.burn_cnft(
         root,
         data_hash,
-        creator_hash,
         nonce,
         index,
         asset_data_hash,
         flags,
+        creator_hash,
         ctx.remaining_accounts,
     )?;
 
-    ctx.accounts.record_burn()?;
+    ctx.accounts.record_burn(nonce, &ctx.bumps)?;
 
     Ok(())
 }
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use mpl_core::{instructions::BurnV1CpiBuilder, ID as CORE_PROGRAM_ID};
+
+use crate::{
+    error::ErrorCode, events::NftBurned, utils::active_season, Config, PlayerProgress, Season,
+};
+
+#[derive(Accounts)]
+pub struct BurnNFT<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        seeds = [b"player", player.key().as_ref()],
+        bump,
+        space = PlayerProgress::DISCRIMINATOR.len() + PlayerProgress::INIT_SPACE,
+    )]
+    pub player_progress: Box<Account<'info, PlayerProgress>>,
+
+    /// CHECK: Collection Account that will be checked by core
+    #[account(
+        mut,
+        constraint = !nft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.nft_collection,
+    )]
+    pub nft_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Asset Account that will be checked by core
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BurnNFT<'info> {
+    pub fn check_season(&mut self) -> Result<()> {
+        let season = active_season(&self.config, self.season.as_deref())?;
+        self.player_progress.sync_season(season);
+
+        Ok(())
+    }
+
+    pub fn init_player_progress(&mut self, bumps: &BurnNFTBumps) -> Result<()> {
+        if self.player_progress.bump == 0 {
+            self.player_progress.set_inner(PlayerProgress {
+                player: self.player.key(),
+                authority: self.authority.key(),
+                bump: bumps.player_progress,
+                ..Default::default()
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn burn_nft(&mut self) -> Result<()> {
+        BurnV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.nft_collection.to_account_info()))
+            .payer(&self.player.to_account_info())
+            .authority(Some(&self.player.to_account_info()))
+            .system_program(Some(&self.system_program.to_account_info()))
+            .invoke()?;
+
+        Ok(())
+    }
+
+    pub fn record_burn(&mut self) -> Result<()> {
+        self.config.total_nfts_burned += 1;
+
+        self.player_progress
+            .record_nft_burn(self.config.burn_reward_points);
+
+        emit!(NftBurned {
+            player: self.player.key(),
+            asset: self.asset.key(),
+            crafting_points: self.config.burn_reward_points,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<BurnNFT>) -> Result<()> {
+    ctx.accounts.init_player_progress(&ctx.bumps)?;
+    ctx.accounts.check_season()?;
+    ctx.accounts.burn_nft()?;
+
+    ctx.accounts.record_burn()?;
+    Ok(())
+}
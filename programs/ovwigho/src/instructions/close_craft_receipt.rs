@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::CraftReceipt;
+
+#[derive(Accounts)]
+pub struct CloseCraftReceipt<'info> {
+    pub player: Signer<'info>,
+
+    /// Whoever paid the receipt's rent, sponsor or player, gets it back
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = player,
+        has_one = rent_payer,
+        seeds = [b"receipt", craft_receipt.asset.as_ref()],
+        bump = craft_receipt.bump,
+    )]
+    pub craft_receipt: Box<Account<'info, CraftReceipt>>,
+}
+
+pub fn handler(_ctx: Context<CloseCraftReceipt>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, Config, MintDrop};
+
+#[derive(Accounts)]
+pub struct CreateDrop<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            b"drop",
+            config.key().as_ref(),
+            &(config.drop_count + 1).to_le_bytes(),
+        ],
+        bump,
+        space = MintDrop::DISCRIMINATOR.len() + MintDrop::INIT_SPACE,
+    )]
+    pub mint_drop: Box<Account<'info, MintDrop>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateDrop<'info> {
+    pub fn create_drop(
+        &mut self,
+        mint_start: Option<i64>,
+        mint_end: Option<i64>,
+        max_supply: Option<u32>,
+        allowlist_root: Option<[u8; 32]>,
+        bumps: &CreateDropBumps,
+    ) -> Result<()> {
+        if let (Some(start), Some(end)) = (mint_start, mint_end) {
+            require!(start < end, ErrorCode::InvalidDropWindow);
+        }
+
+        self.config.drop_count += 1;
+        require!(
+            self.config.cnft_creators.len() <= self.config.max_cnft_creators(),
+            ErrorCode::TooManyCreators
+        );
+
+        self.mint_drop.set_inner(MintDrop {
+            config: self.config.key(),
+            id: self.config.drop_count,
+            mint_start,
+            mint_end,
+            max_supply,
+            total_minted: 0,
+            allowlist_root,
+            bump: bumps.mint_drop,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<CreateDrop>,
+    mint_start: Option<i64>,
+    mint_end: Option<i64>,
+    max_supply: Option<u32>,
+    allowlist_root: Option<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts
+        .create_drop(mint_start, mint_end, max_supply, allowlist_root, &ctx.bumps)?;
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, utils::signer_keys, Config, Guild, MAX_GUILD_MEMBERS};
+
+/// Every initial member signs the transaction, passed as remaining accounts.
+#[derive(Accounts)]
+pub struct CreateGuild<'info> {
+    #[account(mut)]
+    pub leader: Signer<'info>,
+
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = leader,
+        seeds = [b"guild", config.key().as_ref(), leader.key().as_ref()],
+        bump,
+        space = Guild::DISCRIMINATOR.len() + Guild::INIT_SPACE,
+    )]
+    pub guild: Box<Account<'info, Guild>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateGuild<'info> {
+    pub fn create_guild(
+        &mut self,
+        members: Vec<Pubkey>,
+        cnfts_per_craft: u32,
+        signers: &[Pubkey],
+        bumps: &CreateGuildBumps,
+    ) -> Result<()> {
+        require!(
+            members.len() <= MAX_GUILD_MEMBERS,
+            ErrorCode::TooManyGuildMembers
+        );
+        require!(cnfts_per_craft > 0, ErrorCode::InvalidCraftCost);
+        Guild::check_consent(&[], &members, signers)?;
+
+        self.guild.set_inner(Guild {
+            config: self.config.key(),
+            leader: self.leader.key(),
+            members,
+            total_cnfts_burned: 0,
+            burned_leaves_hash: [0; 32],
+            cnfts_per_craft,
+            total_nfts_minted: 0,
+            bump: bumps.guild,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<CreateGuild>,
+    members: Vec<Pubkey>,
+    cnfts_per_craft: u32,
+) -> Result<()> {
+    let signers = signer_keys(ctx.remaining_accounts);
+    ctx.accounts
+        .create_guild(members, cnfts_per_craft, &signers, &ctx.bumps)?;
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, SessionToken, SESSION_SCOPE_ALL};
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        seeds = [b"session", player.key().as_ref(), session_key.as_ref()],
+        bump,
+        space = SessionToken::DISCRIMINATOR.len() + SessionToken::INIT_SPACE,
+    )]
+    pub session_token: Box<Account<'info, SessionToken>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateSession<'info> {
+    pub fn create_session(
+        &mut self,
+        session_key: Pubkey,
+        expires_at: i64,
+        scope: u8,
+        bumps: &CreateSessionBumps,
+    ) -> Result<()> {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            ErrorCode::InvalidSessionExpiry
+        );
+        require!(
+            scope != 0 && scope & !SESSION_SCOPE_ALL == 0,
+            ErrorCode::InvalidSessionScope
+        );
+
+        self.session_token.set_inner(SessionToken {
+            player: self.player.key(),
+            session_key,
+            expires_at,
+            scope,
+            bump: bumps.session_token,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<CreateSession>,
+    session_key: Pubkey,
+    expires_at: i64,
+    scope: u8,
+) -> Result<()> {
+    ctx.accounts
+        .create_session(session_key, expires_at, scope, &ctx.bumps)?;
+
+    Ok(())
+}
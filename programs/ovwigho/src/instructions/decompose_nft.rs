@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use mpl_account_compression::ID as MPL_ACCOUNT_COMPRESSION_ID;
+use mpl_bubblegum::ID as BUBBLEGUM_ID;
+use mpl_core::{instructions::BurnV1CpiBuilder, ID as CORE_PROGRAM_ID};
+use mpl_noop::ID as MPL_NOOP_ID;
+
+use crate::{
+    error::ErrorCode,
+    events::NftDecomposed,
+    utils::{active_season, mint_cnft_leaf, CnftMintAccounts},
+    Config, CraftReceipt, PlayerProgress, Season,
+};
+
+/// Burns a crafted NFT and hands back the cNFTs its receipt records as burned,
+/// less the configured loss. The leaves are a refund of burns that never exceeds
+/// them, not new supply, so the cNFT fee, `cnft_gate`, `drops_only` and drop
+/// supplies that govern `mint_cnft` do not apply.
+#[derive(Accounts)]
+pub struct DecomposeNFT<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        seeds = [b"player", player.key().as_ref()],
+        bump,
+        space = PlayerProgress::DISCRIMINATOR.len() + PlayerProgress::INIT_SPACE,
+    )]
+    pub player_progress: Box<Account<'info, PlayerProgress>>,
+
+    /// CHECK: Collection Account that will be checked by core
+    #[account(
+        mut,
+        constraint = !nft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.nft_collection,
+    )]
+    pub nft_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Asset Account that will be checked by core
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"receipt", asset.key().as_ref()],
+        bump = craft_receipt.bump,
+        constraint = config.decompose_yield(craft_receipt.cnfts_burned) > 0 @ ErrorCode::NothingToDecompose,
+    )]
+    pub craft_receipt: Box<Account<'info, CraftReceipt>>,
+
+    /// CHECK: Collection Account that will be checked by the Bubblegum Program
+    #[account(
+        mut,
+        constraint = !cnft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.cnft_collection,
+    )]
+    pub cnft_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Tree Config checks will be performed by the Bubblegum Program
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: Merkle Tree account that will be checked by the Bubblegum Program
+    #[account(mut, address = config.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: MPL Core CPI Signer account that will be checked by the Bubblegum Program
+    pub mpl_core_cpi_signer: UncheckedAccount<'info>,
+    /// CHECK: MPL NOOP Program checked by the corresponding address
+    #[account(address = MPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: Bubblegum Program checked by the corresponding address
+    #[account(address = BUBBLEGUM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: MPL Account Compression Program checked by the corresponding address
+    #[account(address = MPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DecomposeNFT<'info> {
+    pub fn check_season(&mut self) -> Result<()> {
+        let season = active_season(&self.config, self.season.as_deref())?;
+        self.player_progress.sync_season(season);
+
+        Ok(())
+    }
+
+    pub fn init_player_progress(&mut self, bumps: &DecomposeNFTBumps) -> Result<()> {
+        if self.player_progress.bump == 0 {
+            self.player_progress.set_inner(PlayerProgress {
+                player: self.player.key(),
+                authority: self.authority.key(),
+                bump: bumps.player_progress,
+                ..Default::default()
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn burn_nft(&mut self) -> Result<()> {
+        BurnV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.nft_collection.to_account_info()))
+            .payer(&self.player.to_account_info())
+            .authority(Some(&self.player.to_account_info()))
+            .system_program(Some(&self.system_program.to_account_info()))
+            .invoke()?;
+
+        Ok(())
+    }
+
+    pub fn mint_cnfts(&mut self, name: String, uri: String, symbol: String) -> Result<u32> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let player = &self.player.to_account_info();
+        let accounts = CnftMintAccounts {
+            bubblegum_program: &self.bubblegum_program.to_account_info(),
+            tree_config: &self.tree_config.to_account_info(),
+            leaf_owner: player,
+            leaf_delegate: player,
+            merkle_tree: &self.merkle_tree.to_account_info(),
+            payer: player,
+            config: &self.config.to_account_info(),
+            cnft_collection: &self.cnft_collection.to_account_info(),
+            mpl_core_cpi_signer: &self.mpl_core_cpi_signer.to_account_info(),
+            log_wrapper: &self.log_wrapper.to_account_info(),
+            compression_program: &self.compression_program.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            mpl_core_program: &self.core_program.to_account_info(),
+        };
+
+        let count = self.config.decompose_yield(self.craft_receipt.cnfts_burned);
+        for _ in 0..count {
+            mint_cnft_leaf(
+                &accounts,
+                self.config.leaf_metadata(
+                    self.config.key(),
+                    name.clone(),
+                    symbol.clone(),
+                    uri.clone(),
+                ),
+                signer_seeds,
+            )?;
+        }
+
+        Ok(count)
+    }
+
+    pub fn record_decompose(&mut self, cnfts_minted: u32) -> Result<()> {
+        self.config.total_nfts_burned += 1;
+        self.config.total_cnfts_minted += cnfts_minted;
+
+        emit!(NftDecomposed {
+            player: self.player.key(),
+            asset: self.asset.key(),
+            cnfts_minted,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<DecomposeNFT>,
+    name: String,
+    uri: String,
+    symbol: String,
+) -> Result<()> {
+    ctx.accounts.init_player_progress(&ctx.bumps)?;
+    ctx.accounts.check_season()?;
+    ctx.accounts.burn_nft()?;
+
+    let cnfts_minted = ctx.accounts.mint_cnfts(name, uri, symbol)?;
+
+    ctx.accounts.record_decompose(cnfts_minted)?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use mpl_core::{instructions::CreateCollectionV2CpiBuilder, ID as CORE_PROGRAM_ID};
+
+use crate::{error::ErrorCode, Config, CreateNftCollectionArgs};
+
+/// Core only accepts permanent plugins when a collection is created, so an
+/// existing NFT collection is upgraded by replacing it with a new collection
+/// that carries the moderation plugins. Assets crafted into the previous
+/// collection are left untouched.
+#[derive(Accounts)]
+pub struct EnableModeration<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.moderation @ ErrorCode::ModerationAlreadyEnabled,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        constraint = nft_collection.data_is_empty() @ ErrorCode::CollectionAlreadyInitialized
+    )]
+    pub nft_collection: Signer<'info>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EnableModeration<'info> {
+    pub fn create_nft_collection(&mut self, mut args: CreateNftCollectionArgs) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        args.moderation = true;
+
+        CreateCollectionV2CpiBuilder::new(&self.core_program.to_account_info())
+            .collection(&self.nft_collection.to_account_info())
+            .update_authority(Some(&self.config.to_account_info()))
+            .payer(&self.authority.to_account_info())
+            .system_program(&self.system_program.to_account_info())
+            .name(args.name.clone())
+            .uri(args.uri.clone())
+            .plugins(args.plugins(self.config.key()))
+            .invoke_signed(signer_seeds)?;
+
+        // Edition numbers and the max supply start over with the new collection.
+        self.config.nft_collection = self.nft_collection.key();
+        self.config.nft_max_supply = args.max_supply;
+        self.config.total_editions = 0;
+        self.config.moderation = true;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<EnableModeration>, args: CreateNftCollectionArgs) -> Result<()> {
+    ctx.accounts.create_nft_collection(args)?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::Config;
+
+/// Clears the active season so gameplay is no longer time gated.
+#[derive(Accounts)]
+pub struct EndSeason<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+pub fn handler(ctx: Context<EndSeason>) -> Result<()> {
+    ctx.accounts.config.active_season = None;
+
+    Ok(())
+}
@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use mpl_core::{instructions::CreateV2CpiBuilder, types::Attribute, ID as CORE_PROGRAM_ID};
+
+use crate::{
+    error::ErrorCode,
+    utils::{active_season, check_gate, collect_payment, TokenPaymentAccounts},
+    Config, CraftReceipt, Guild, PlayerProgress, Recipe, Season,
+};
+
+/// Spends the guild's pooled burns to mint an NFT owned by one of its
+/// members. The leader pays the usual crafting fees and the recipient must
+/// meet the NFT gate.
+#[derive(Accounts)]
+pub struct GuildCraft<'info> {
+    #[account(mut)]
+    pub leader: Signer<'info>,
+
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+        constraint = config.has_supply_left() @ ErrorCode::MaxSupplyReached,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", config.key().as_ref()],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = leader,
+        has_one = config,
+        seeds = [b"guild", config.key().as_ref(), leader.key().as_ref()],
+        bump = guild.bump,
+    )]
+    pub guild: Box<Account<'info, Guild>>,
+
+    #[account(
+        constraint = guild.members.contains(&recipient.key()) @ ErrorCode::InvalidGuildRecipient,
+    )]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", recipient.key().as_ref()],
+        bump = recipient_progress.bump,
+    )]
+    pub recipient_progress: Box<Account<'info, PlayerProgress>>,
+
+    /// CHECK: Collection Account that will be checked by core
+    #[account(
+        mut,
+        constraint = !nft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.nft_collection,
+    )]
+    pub nft_collection: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = asset.data_is_empty() @ ErrorCode::AssetAlreadyInitialized
+    )]
+    pub asset: Signer<'info>,
+
+    #[account(
+        init,
+        payer = leader,
+        seeds = [b"receipt", asset.key().as_ref()],
+        bump,
+        space = CraftReceipt::DISCRIMINATOR.len() + CraftReceipt::INIT_SPACE,
+    )]
+    pub craft_receipt: Box<Account<'info, CraftReceipt>>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub leader_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// CHECK: The recipient's Core asset or token account for the gate, checked in `check_gate`
+    pub gate_account: Option<UncheckedAccount<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GuildCraft<'info> {
+    pub fn check_season(&mut self) -> Result<()> {
+        let season = active_season(&self.config, self.season.as_deref())?;
+        self.recipient_progress.sync_season(season);
+
+        require!(
+            self.guild.total_cnfts_burned >= self.craft_cost(),
+            ErrorCode::NotEnoughBurns
+        );
+
+        Ok(())
+    }
+
+    pub fn craft_cost(&self) -> u32 {
+        self.guild.craft_cost(
+            self.config
+                .craft_cost(self.season.as_deref().map(|season| &**season)),
+        )
+    }
+
+    pub fn check_gate(&self) -> Result<()> {
+        check_gate(
+            &self.recipient.key(),
+            self.config.nft_gate.as_ref(),
+            self.gate_account.as_deref(),
+        )
+    }
+
+    pub fn pay_fee(&mut self) -> Result<()> {
+        collect_payment(
+            &TokenPaymentAccounts {
+                config: &self.config,
+                payer: &self.leader.to_account_info(),
+                payment_mint: self.payment_mint.as_deref(),
+                payer_token_account: self.leader_token_account.as_deref(),
+                treasury_token_account: self.treasury_token_account.as_deref(),
+                token_program: self.token_program.as_ref(),
+            },
+            &self.treasury.to_account_info(),
+            &self.system_program.to_account_info(),
+            self.config
+                .payment(self.config.nft_mint_fee, self.config.nft_token_price),
+        )
+    }
+
+    pub fn mint_nft(&mut self, name: String, uri: String) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let plugins = self.config.asset_plugins(
+            self.config.key(),
+            self.config.total_editions + 1,
+            vec![
+                Attribute {
+                    key: "Guild".to_string(),
+                    value: self.guild.key().to_string(),
+                },
+                Attribute {
+                    key: "Player".to_string(),
+                    value: self.recipient.key().to_string(),
+                },
+                Attribute {
+                    key: "Collection".to_string(),
+                    value: self.nft_collection.key().to_string(),
+                },
+                Attribute {
+                    key: "Level".to_string(),
+                    value: "1".to_string(),
+                },
+                Attribute {
+                    key: "XP".to_string(),
+                    value: "0".to_string(),
+                },
+            ],
+        );
+
+        CreateV2CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.nft_collection.to_account_info()))
+            .authority(Some(&self.config.to_account_info()))
+            .payer(&self.leader.to_account_info())
+            .owner(Some(&self.recipient.to_account_info()))
+            .update_authority(None)
+            .system_program(&self.system_program.to_account_info())
+            .name(name)
+            .uri(uri)
+            .plugins(plugins)
+            .external_plugin_adapters(vec![])
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    pub fn create_receipt(&mut self, bumps: &GuildCraftBumps) -> Result<()> {
+        self.craft_receipt.set_inner(CraftReceipt {
+            player: self.recipient.key(),
+            asset: self.asset.key(),
+            recipe: Recipe::Guild {
+                guild: self.guild.key(),
+            },
+            cnfts_burned: self.craft_cost(),
+            slot: Clock::get()?.slot,
+            burned_leaves_hash: self.guild.burned_leaves_hash,
+            rent_payer: self.leader.key(),
+            bump: bumps.craft_receipt,
+        });
+
+        Ok(())
+    }
+
+    pub fn record_craft(&mut self) -> Result<()> {
+        self.guild.total_cnfts_burned -= self.craft_cost();
+        self.guild.total_nfts_minted += 1;
+
+        self.config.record_craft(&mut self.recipient_progress);
+        self.recipient_progress.record_nft_mint();
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<GuildCraft>, name: String, uri: String) -> Result<()> {
+    ctx.accounts.check_season()?;
+    ctx.accounts.check_gate()?;
+    ctx.accounts.pay_fee()?;
+    ctx.accounts.mint_nft(name, uri)?;
+    ctx.accounts.create_receipt(&ctx.bumps)?;
+    ctx.accounts.record_craft()?;
+
+    Ok(())
+}
@@ -1,26 +1,109 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
 use mpl_account_compression::ID as MPL_ACCOUNT_COMPRESSION_ID;
 use mpl_bubblegum::{instructions::CreateTreeConfigV2CpiBuilder, ID as BUBBLEGUM_ID};
 use mpl_core::{
     instructions::CreateCollectionV2CpiBuilder,
-    types::{BubblegumV2, PluginAuthorityPair},
+    types::{
+        BubblegumV2, Creator, MasterEdition, PermanentBurnDelegate, PermanentFreezeDelegate,
+        Plugin, PluginAuthority, PluginAuthorityPair, Royalties, RuleSet,
+    },
     ID as CORE_PROGRAM_ID,
 };
 use mpl_noop::ID as MPL_NOOP_ID;
 
 use crate::{error::ErrorCode, Config};
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RoyaltyCreator {
+    pub address: Pubkey,
+    pub percentage: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum RoyaltyRuleSet {
+    None,
+    ProgramAllowList(Vec<Pubkey>),
+    ProgramDenyList(Vec<Pubkey>),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RoyaltyArgs {
+    pub basis_points: u16,
+    pub creators: Vec<RoyaltyCreator>,
+    pub rule_set: RoyaltyRuleSet,
+}
+
+impl From<RoyaltyArgs> for Royalties {
+    fn from(args: RoyaltyArgs) -> Self {
+        Royalties {
+            basis_points: args.basis_points,
+            creators: args
+                .creators
+                .into_iter()
+                .map(|creator| Creator {
+                    address: creator.address,
+                    percentage: creator.percentage,
+                })
+                .collect(),
+            rule_set: match args.rule_set {
+                RoyaltyRuleSet::None => RuleSet::None,
+                RoyaltyRuleSet::ProgramAllowList(programs) => RuleSet::ProgramAllowList(programs),
+                RoyaltyRuleSet::ProgramDenyList(programs) => RuleSet::ProgramDenyList(programs),
+            },
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateCnftCollectionArgs {
     pub name: String,
     pub uri: String,
+    pub royalties: Option<RoyaltyArgs>,
+    pub is_mutable: bool,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateNftCollectionArgs {
     pub name: String,
     pub uri: String,
+    pub max_supply: Option<u32>,
+    pub royalties: Option<RoyaltyArgs>,
+    pub moderation: bool,
+}
+
+impl CreateNftCollectionArgs {
+    pub fn plugins(&self, config: Pubkey) -> Vec<PluginAuthorityPair> {
+        let mut plugins = vec![PluginAuthorityPair {
+            plugin: Plugin::MasterEdition(MasterEdition {
+                max_supply: self.max_supply,
+                name: None,
+                uri: None,
+            }),
+            authority: None,
+        }];
+
+        if let Some(royalties) = &self.royalties {
+            plugins.push(PluginAuthorityPair {
+                plugin: Plugin::Royalties(royalties.clone().into()),
+                authority: None,
+            });
+        }
+
+        if self.moderation {
+            plugins.push(PluginAuthorityPair {
+                plugin: Plugin::PermanentBurnDelegate(PermanentBurnDelegate {}),
+                authority: Some(PluginAuthority::Address { address: config }),
+            });
+            plugins.push(PluginAuthorityPair {
+                plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: false }),
+                authority: Some(PluginAuthority::Address { address: config }),
+            });
+        }
+
+        plugins
+    }
 }
 
 #[derive(Accounts)]
@@ -37,6 +120,13 @@ pub struct Initialize<'info> {
     )]
     pub config: Box<Account<'info, Config>>,
 
+    #[account(
+        mut,
+        seeds = [b"treasury", config.key().as_ref()],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
     #[account(
         mut,
         constraint = cnft_collection.data_is_empty() @ ErrorCode::CollectionAlreadyInitialized
@@ -73,15 +163,35 @@ pub struct Initialize<'info> {
 
 impl<'info> Initialize<'info> {
     pub fn initialize(&mut self, bumps: &InitializeBumps) -> Result<()> {
-        self.config.set_inner(Config {
-            authority: self.authority.key(),
-            cnft_collection: self.cnft_collection.key(),
-            nft_collection: self.nft_collection.key(),
-            merkle_tree: self.merkle_tree.key(),
-            total_cnfts_minted: 0,
-            total_nfts_minted: 0,
-            bump: bumps.config,
-        });
+        self.config.set_inner(Config::new(
+            self.authority.key(),
+            self.cnft_collection.key(),
+            self.nft_collection.key(),
+            self.merkle_tree.key(),
+            self.config.key(),
+            bumps.treasury,
+            bumps.config,
+        ));
+
+        Ok(())
+    }
+
+    pub fn fund_treasury(&mut self) -> Result<()> {
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_exempt_minimum.saturating_sub(self.treasury.lamports());
+
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.authority.to_account_info(),
+                        to: self.treasury.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
 
         Ok(())
     }
@@ -89,7 +199,7 @@ impl<'info> Initialize<'info> {
     pub fn init_merkle_tree(&mut self, max_depth: u32, max_buffer_size: u32) -> Result<()> {
         let seeds = &[
             &b"config"[..],
-            &self.authority.key.as_ref(),
+            self.authority.key.as_ref(),
             &[self.config.bump],
         ];
 
@@ -125,13 +235,24 @@ impl<'info> Initialize<'info> {
         bumps: &InitializeBumps,
         args: CreateCnftCollectionArgs,
     ) -> Result<()> {
-        let seeds = &[
-            &b"config"[..],
-            &self.authority.key.as_ref(),
-            &[bumps.config],
-        ];
+        let seeds = &[&b"config"[..], self.authority.key.as_ref(), &[bumps.config]];
         let signer_seeds = &[&seeds[..]];
 
+        self.config.cnft_is_mutable = args.is_mutable;
+
+        let mut plugins = vec![PluginAuthorityPair {
+            plugin: Plugin::BubblegumV2(BubblegumV2 {}),
+            authority: None,
+        }];
+
+        if let Some(royalties) = args.royalties {
+            self.config.cnft_seller_fee_basis_points = royalties.basis_points;
+            plugins.push(PluginAuthorityPair {
+                plugin: Plugin::Royalties(royalties.into()),
+                authority: None,
+            });
+        }
+
         CreateCollectionV2CpiBuilder::new(&self.core_program.to_account_info())
             .collection(&self.cnft_collection.to_account_info())
             .update_authority(Some(&self.config.to_account_info()))
@@ -139,10 +260,7 @@ impl<'info> Initialize<'info> {
             .system_program(&self.system_program.to_account_info())
             .name(args.name)
             .uri(args.uri)
-            .plugins(vec![PluginAuthorityPair {
-                plugin: mpl_core::types::Plugin::BubblegumV2(BubblegumV2 {}),
-                authority: None,
-            }])
+            .plugins(plugins)
             .external_plugin_adapters(vec![])
             .invoke_signed(signer_seeds)?;
 
@@ -154,13 +272,14 @@ impl<'info> Initialize<'info> {
         args: CreateNftCollectionArgs,
         bumps: &InitializeBumps,
     ) -> Result<()> {
-        let seeds = &[
-            &b"config"[..],
-            &self.authority.key.as_ref(),
-            &[bumps.config],
-        ];
+        let seeds = &[&b"config"[..], self.authority.key.as_ref(), &[bumps.config]];
         let signer_seeds = &[&seeds[..]];
 
+        self.config.nft_max_supply = args.max_supply;
+        self.config.moderation = args.moderation;
+
+        let plugins = args.plugins(self.config.key());
+
         CreateCollectionV2CpiBuilder::new(&self.core_program.to_account_info())
             .collection(&self.nft_collection.to_account_info())
             .update_authority(Some(&self.config.to_account_info()))
@@ -168,7 +287,7 @@ impl<'info> Initialize<'info> {
             .system_program(&self.system_program.to_account_info())
             .name(args.name)
             .uri(args.uri)
-            .plugins(vec![])
+            .plugins(plugins)
             .invoke_signed(signer_seeds)?;
 
         Ok(())
@@ -183,8 +302,77 @@ pub fn handler(
     nft_args: CreateNftCollectionArgs,
 ) -> Result<()> {
     ctx.accounts.initialize(&ctx.bumps)?;
+    ctx.accounts.fund_treasury()?;
     ctx.accounts.init_merkle_tree(max_depth, max_buffer_size)?;
     ctx.accounts.create_cnft_collection(&ctx.bumps, cnft_args)?;
     ctx.accounts.create_nft_collection(nft_args, &ctx.bumps)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpl_core::types::PluginType;
+
+    fn nft_args(royalties: Option<RoyaltyArgs>, moderation: bool) -> CreateNftCollectionArgs {
+        CreateNftCollectionArgs {
+            name: "NFTs".to_string(),
+            uri: "https://example.com/nfts.json".to_string(),
+            max_supply: Some(100),
+            royalties,
+            moderation,
+        }
+    }
+
+    #[test]
+    fn royalty_args_become_a_core_royalties_plugin() {
+        let creator = Pubkey::new_unique();
+        let denied = Pubkey::new_unique();
+        let args = nft_args(
+            Some(RoyaltyArgs {
+                basis_points: 500,
+                creators: vec![RoyaltyCreator {
+                    address: creator,
+                    percentage: 100,
+                }],
+                rule_set: RoyaltyRuleSet::ProgramDenyList(vec![denied]),
+            }),
+            false,
+        );
+
+        let plugins = args.plugins(Pubkey::new_unique());
+        assert_eq!(plugins.len(), 2);
+
+        let Plugin::Royalties(royalties) = &plugins[1].plugin else {
+            panic!("expected a royalties plugin");
+        };
+        assert_eq!(royalties.basis_points, 500);
+        assert_eq!(royalties.creators[0].address, creator);
+        assert_eq!(royalties.creators[0].percentage, 100);
+        assert_eq!(royalties.rule_set, RuleSet::ProgramDenyList(vec![denied]));
+    }
+
+    #[test]
+    fn moderated_collections_are_created_with_permanent_delegates() {
+        let config = Pubkey::new_unique();
+        let kinds = |moderation| {
+            nft_args(None, moderation)
+                .plugins(config)
+                .into_iter()
+                .map(|pair| (PluginType::from(&pair.plugin), pair.authority))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(kinds(false), vec![(PluginType::MasterEdition, None)]);
+
+        let delegate = Some(PluginAuthority::Address { address: config });
+        assert_eq!(
+            kinds(true),
+            vec![
+                (PluginType::MasterEdition, None),
+                (PluginType::PermanentBurnDelegate, delegate.clone()),
+                (PluginType::PermanentFreezeDelegate, delegate),
+            ]
+        );
+    }
+}
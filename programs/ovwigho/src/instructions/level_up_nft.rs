@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    fetch_asset_plugin,
+    instructions::{UpdatePluginV1CpiBuilder, UpdateV1CpiBuilder},
+    types::{Attribute, Attributes, Plugin, PluginType},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    error::ErrorCode,
+    events::NftLeveledUp,
+    utils::{active_season, asset_owner},
+    Config, PlayerProgress, Season,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum LevelUpPayment {
+    CraftingPoints,
+    BurnedCnfts,
+}
+
+#[derive(Accounts)]
+pub struct LevelUpNFT<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player.key().as_ref()],
+        bump = player_progress.bump,
+    )]
+    pub player_progress: Box<Account<'info, PlayerProgress>>,
+
+    /// CHECK: Collection Account that will be checked by core
+    #[account(
+        mut,
+        constraint = !nft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.nft_collection,
+    )]
+    pub nft_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Asset Account that will be checked by core
+    #[account(
+        mut,
+        constraint = asset_owner(&asset) == Some(player.key()) @ ErrorCode::AssetOwnerMismatch,
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> LevelUpNFT<'info> {
+    pub fn check_season(&mut self) -> Result<()> {
+        let season = active_season(&self.config, self.season.as_deref())?;
+        self.player_progress.sync_season(season);
+
+        Ok(())
+    }
+
+    pub fn pay(&mut self, payment: LevelUpPayment) -> Result<u32> {
+        match payment {
+            LevelUpPayment::CraftingPoints => {
+                let cost = self.config.level_up_points;
+                require!(cost > 0, ErrorCode::LevelUpDisabled);
+                self.player_progress.crafting_points = self
+                    .player_progress
+                    .crafting_points
+                    .checked_sub(cost)
+                    .ok_or(ErrorCode::NotEnoughPoints)?;
+                Ok(cost)
+            }
+            LevelUpPayment::BurnedCnfts => {
+                let cost = self.config.level_up_burns;
+                require!(cost > 0, ErrorCode::LevelUpDisabled);
+                self.player_progress.total_cnfts_burned = self
+                    .player_progress
+                    .total_cnfts_burned
+                    .checked_sub(cost)
+                    .ok_or(ErrorCode::NotEnoughBurns)?;
+                Ok(cost)
+            }
+        }
+    }
+
+    pub fn level_up(&mut self, xp_gained: u32) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let (_, mut attributes, _) = fetch_asset_plugin::<Attributes>(
+            &self.asset.to_account_info(),
+            PluginType::Attributes,
+        )?;
+
+        let (level, xp) = apply_level_up(&mut attributes, xp_gained)?;
+
+        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.nft_collection.to_account_info()))
+            .payer(&self.player.to_account_info())
+            .authority(Some(&self.config.to_account_info()))
+            .system_program(&self.system_program.to_account_info())
+            .plugin(Plugin::Attributes(attributes))
+            .invoke_signed(signer_seeds)?;
+
+        if !self.config.level_uri_prefix.is_empty() {
+            UpdateV1CpiBuilder::new(&self.core_program.to_account_info())
+                .asset(&self.asset.to_account_info())
+                .collection(Some(&self.nft_collection.to_account_info()))
+                .payer(&self.player.to_account_info())
+                .authority(Some(&self.config.to_account_info()))
+                .system_program(&self.system_program.to_account_info())
+                .new_uri(format!("{}{}.json", self.config.level_uri_prefix, level))
+                .invoke_signed(signer_seeds)?;
+        }
+
+        emit!(NftLeveledUp {
+            player: self.player.key(),
+            asset: self.asset.key(),
+            level,
+            xp,
+        });
+
+        Ok(())
+    }
+}
+
+/// Bumps the asset's level and adds the gained XP, returning the new values.
+fn apply_level_up(attributes: &mut Attributes, xp_gained: u32) -> Result<(u32, u32)> {
+    let level = read_attribute(attributes, "Level")
+        .unwrap_or(1)
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let xp = read_attribute(attributes, "XP")
+        .unwrap_or(0)
+        .checked_add(xp_gained)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    write_attribute(attributes, "Level", level);
+    write_attribute(attributes, "XP", xp);
+
+    Ok((level, xp))
+}
+
+fn read_attribute(attributes: &Attributes, key: &str) -> Option<u32> {
+    attributes
+        .attribute_list
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .and_then(|attribute| attribute.value.parse().ok())
+}
+
+fn write_attribute(attributes: &mut Attributes, key: &str, value: u32) {
+    match attributes
+        .attribute_list
+        .iter_mut()
+        .find(|attribute| attribute.key == key)
+    {
+        Some(attribute) => attribute.value = value.to_string(),
+        None => attributes.attribute_list.push(Attribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+pub fn handler(ctx: Context<LevelUpNFT>, payment: LevelUpPayment) -> Result<()> {
+    ctx.accounts.check_season()?;
+    let xp_gained = ctx.accounts.pay(payment)?;
+    ctx.accounts.level_up(xp_gained)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_up_adds_xp_and_rejects_overflow() {
+        let mut attributes = Attributes {
+            attribute_list: vec![Attribute {
+                key: "Player".to_string(),
+                value: "someone".to_string(),
+            }],
+        };
+
+        assert_eq!(apply_level_up(&mut attributes, 10).unwrap(), (2, 10));
+        assert_eq!(apply_level_up(&mut attributes, 5).unwrap(), (3, 15));
+        assert_eq!(read_attribute(&attributes, "XP"), Some(15));
+
+        write_attribute(&mut attributes, "XP", u32::MAX);
+        assert!(apply_level_up(&mut attributes, 1).is_err());
+        assert_eq!(read_attribute(&attributes, "Level"), Some(3));
+
+        write_attribute(&mut attributes, "Level", u32::MAX);
+        assert!(apply_level_up(&mut attributes, 0).is_err());
+    }
+}
@@ -0,0 +1,267 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{BurnV1CpiBuilder, CreateV2CpiBuilder},
+    types::Attribute,
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    error::ErrorCode,
+    events::NftsMerged,
+    utils::{active_season, check_gate},
+    Config, CraftReceipt, PlayerProgress, Recipe, Season, Tier,
+};
+
+/// Burns `target_tier.merge_cost` assets, passed as remaining accounts, from the
+/// collection of the preceding tier and mints one asset into `target_tier`.
+/// Tier 1 merges consume assets from `config.nft_collection`.
+#[derive(Accounts)]
+pub struct MergeNFTs<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        seeds = [b"player", player.key().as_ref()],
+        bump,
+        space = PlayerProgress::DISCRIMINATOR.len() + PlayerProgress::INIT_SPACE,
+    )]
+    pub player_progress: Box<Account<'info, PlayerProgress>>,
+
+    #[account(
+        mut,
+        seeds = [b"tier", config.key().as_ref(), &[target_tier.index]],
+        bump = target_tier.bump,
+        constraint = target_tier.has_supply_left() @ ErrorCode::MaxSupplyReached,
+    )]
+    pub target_tier: Box<Account<'info, Tier>>,
+
+    #[account(
+        mut,
+        seeds = [b"tier", config.key().as_ref(), &[target_tier.index - 1]],
+        bump = source_tier.bump,
+    )]
+    pub source_tier: Option<Box<Account<'info, Tier>>>,
+
+    /// CHECK: Collection of the preceding tier, checked against the config or source tier
+    #[account(
+        mut,
+        constraint = source_collection.key() == source_tier
+            .as_ref()
+            .map_or(config.nft_collection, |tier| tier.collection)
+            @ ErrorCode::InvalidSourceTier,
+    )]
+    pub source_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Collection Account that will be checked by core
+    #[account(mut, address = target_tier.collection)]
+    pub target_collection: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = asset.data_is_empty() @ ErrorCode::AssetAlreadyInitialized
+    )]
+    pub asset: Signer<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        seeds = [b"receipt", asset.key().as_ref()],
+        bump,
+        space = CraftReceipt::DISCRIMINATOR.len() + CraftReceipt::INIT_SPACE,
+    )]
+    pub craft_receipt: Box<Account<'info, CraftReceipt>>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    /// CHECK: The player's Core asset or token account for the gate, checked in `check_gate`
+    pub gate_account: Option<UncheckedAccount<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MergeNFTs<'info> {
+    pub fn check_season(&mut self) -> Result<()> {
+        let season = active_season(&self.config, self.season.as_deref())?;
+        self.player_progress.sync_season(season);
+
+        Ok(())
+    }
+
+    pub fn check_gate(&self) -> Result<()> {
+        check_gate(
+            &self.player.key(),
+            self.config.nft_gate.as_ref(),
+            self.gate_account.as_deref(),
+        )
+    }
+
+    pub fn init_player_progress(&mut self, bumps: &MergeNFTsBumps) -> Result<()> {
+        if self.player_progress.bump == 0 {
+            self.player_progress.set_inner(PlayerProgress {
+                player: self.player.key(),
+                authority: self.authority.key(),
+                bump: bumps.player_progress,
+                ..Default::default()
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Burns the merged assets and returns the hash of their ids for the receipt.
+    pub fn burn_nfts(&mut self, assets: &[AccountInfo<'info>]) -> Result<[u8; 32]> {
+        require!(
+            self.source_tier.is_some() == (self.target_tier.index > 1),
+            ErrorCode::InvalidSourceTier
+        );
+        require!(
+            assets.len() == self.target_tier.merge_cost as usize,
+            ErrorCode::InvalidMergeCount
+        );
+
+        let core_program = &self.core_program.to_account_info();
+        let source_collection = &self.source_collection.to_account_info();
+        let player = &self.player.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+
+        let mut burned_hash = [0; 32];
+        for asset in assets {
+            burned_hash = CraftReceipt::chain_burn(&burned_hash, asset.key);
+
+            BurnV1CpiBuilder::new(core_program)
+                .asset(asset)
+                .collection(Some(source_collection))
+                .payer(player)
+                .authority(Some(player))
+                .system_program(Some(system_program))
+                .invoke()?;
+        }
+
+        Ok(burned_hash)
+    }
+
+    pub fn mint_nft(&mut self, name: String, uri: String) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let plugins = self.config.asset_plugins(
+            self.config.key(),
+            self.target_tier.total_minted + 1,
+            vec![
+                Attribute {
+                    key: "Player".to_string(),
+                    value: self.player.key().to_string(),
+                },
+                Attribute {
+                    key: "Collection".to_string(),
+                    value: self.target_collection.key().to_string(),
+                },
+                Attribute {
+                    key: "Tier".to_string(),
+                    value: self.target_tier.index.to_string(),
+                },
+            ],
+        );
+
+        CreateV2CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.target_collection.to_account_info()))
+            .authority(Some(&self.config.to_account_info()))
+            .payer(&self.player.to_account_info())
+            .owner(Some(&self.player.to_account_info()))
+            .update_authority(None)
+            .system_program(&self.system_program.to_account_info())
+            .name(name)
+            .uri(uri)
+            .plugins(plugins)
+            .external_plugin_adapters(vec![])
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    pub fn create_receipt(
+        &mut self,
+        burned_leaves_hash: [u8; 32],
+        bumps: &MergeNFTsBumps,
+    ) -> Result<()> {
+        self.craft_receipt.set_inner(CraftReceipt {
+            player: self.player.key(),
+            asset: self.asset.key(),
+            recipe: Recipe::Merge {
+                tier: self.target_tier.index,
+                nfts_burned: self.target_tier.merge_cost,
+            },
+            cnfts_burned: 0,
+            slot: Clock::get()?.slot,
+            burned_leaves_hash,
+            rent_payer: self.player.key(),
+            bump: bumps.craft_receipt,
+        });
+
+        Ok(())
+    }
+
+    pub fn record_merge(&mut self) -> Result<()> {
+        let merge_cost = self.target_tier.merge_cost as u32;
+        let target = self.target_tier.index as usize - 1;
+
+        if let Some(source_tier) = self.source_tier.as_mut() {
+            source_tier.total_burned += merge_cost;
+        }
+
+        self.config.total_nfts_burned += merge_cost;
+        self.config.record_soulbound(&mut self.player_progress);
+
+        self.target_tier.total_minted += 1;
+        self.player_progress.total_nfts_minted += 1;
+        self.player_progress.tier_nfts_minted[target] += 1;
+
+        emit!(NftsMerged {
+            player: self.player.key(),
+            asset: self.asset.key(),
+            tier: self.target_tier.index,
+            nfts_burned: self.target_tier.merge_cost,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MergeNFTs<'info>>,
+    name: String,
+    uri: String,
+) -> Result<()> {
+    ctx.accounts.init_player_progress(&ctx.bumps)?;
+    ctx.accounts.check_season()?;
+    ctx.accounts.check_gate()?;
+    let burned_hash = ctx.accounts.burn_nfts(ctx.remaining_accounts)?;
+    ctx.accounts.mint_nft(name, uri)?;
+    ctx.accounts.create_receipt(burned_hash, &ctx.bumps)?;
+
+    ctx.accounts.record_merge()?;
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{error::ErrorCode, Config, LegacyConfig};
+
+/// Rewrites a config created before the crafting features into the current
+/// layout, growing the account and funding the treasury it now expects.
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: still in the legacy layout, validated in `migrate`
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", config.key().as_ref()],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateConfig<'info> {
+    pub fn read_legacy(&self) -> Result<LegacyConfig> {
+        let data = self.config.try_borrow_data()?;
+        require!(
+            data.len() == Config::DISCRIMINATOR.len() + LegacyConfig::LEN
+                && data.starts_with(Config::DISCRIMINATOR),
+            ErrorCode::InvalidLegacyConfig
+        );
+
+        let legacy = LegacyConfig::deserialize(&mut &data[Config::DISCRIMINATOR.len()..])?;
+        require_keys_eq!(
+            legacy.authority,
+            self.authority.key(),
+            ErrorCode::InvalidLegacyConfig
+        );
+
+        Ok(legacy)
+    }
+
+    pub fn top_up(&self, to: &AccountInfo<'info>, space: usize) -> Result<()> {
+        let shortfall = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(to.lamports());
+
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.authority.to_account_info(),
+                        to: to.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn migrate(&mut self, bumps: &MigrateConfigBumps) -> Result<()> {
+        let config = self
+            .read_legacy()?
+            .migrate(self.config.key(), bumps.treasury);
+
+        let space = Config::DISCRIMINATOR.len() + Config::INIT_SPACE;
+        self.top_up(&self.config.to_account_info(), space)?;
+        self.config.resize(space)?;
+        config.try_serialize(&mut &mut self.config.try_borrow_mut_data()?[..])?;
+
+        self.top_up(&self.treasury.to_account_info(), 0)?;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    ctx.accounts.migrate(&ctx.bumps)?;
+    Ok(())
+}
@@ -1,16 +1,42 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use mpl_account_compression::ID as MPL_ACCOUNT_COMPRESSION_ID;
-use mpl_bubblegum::instructions::MintV2CpiBuilder;
-use mpl_bubblegum::types::{MetadataArgsV2, TokenStandard};
 use mpl_bubblegum::ID as BUBBLEGUM_ID;
 use mpl_noop::ID as MPL_NOOP_ID;
 
-use crate::{error::ErrorCode, Config};
+use crate::{
+    error::ErrorCode,
+    utils::{
+        active_season, authorize_player, check_gate, collect_payment, delegates_to_config,
+        mint_cnft_leaf, CnftMintAccounts, TokenPaymentAccounts,
+    },
+    AllowlistClaim, Config, MintDrop, Season, SessionToken, SESSION_SCOPE_MINT_CNFT,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AllowlistProof {
+    pub allowance: u32,
+    pub proof: Vec<[u8; 32]>,
+}
 
 #[derive(Accounts)]
 pub struct MintcNFT<'info> {
-    #[account(mut)]
-    pub player: Signer<'info>,
+    pub player: SystemAccount<'info>,
+
+    /// The player, or a session key holding a `SessionToken` from the player
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"session", player.key().as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Box<Account<'info, SessionToken>>>,
+
+    #[account(
+        mut,
+        constraint = config.can_pay_for(&payer.key(), &signer.key()) @ ErrorCode::SponsorNotAllowed,
+    )]
+    pub payer: Signer<'info>,
 
     pub authority: SystemAccount<'info>,
 
@@ -21,6 +47,41 @@ pub struct MintcNFT<'info> {
     )]
     pub config: Box<Account<'info, Config>>,
 
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    #[account(
+        mut,
+        seeds = [b"drop", config.key().as_ref(), &mint_drop.id.to_le_bytes()],
+        bump = mint_drop.bump,
+    )]
+    pub mint_drop: Option<Box<Account<'info, MintDrop>>>,
+
+    /// Tracks how much of the player's allowance has been minted, for
+    /// allowlisted drops; only valid alongside `mint_drop`
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            b"claim",
+            mint_drop.as_ref().ok_or(ErrorCode::UnexpectedAllowlistClaim)?.key().as_ref(),
+            player.key().as_ref(),
+        ],
+        bump,
+        space = AllowlistClaim::DISCRIMINATOR.len() + AllowlistClaim::INIT_SPACE,
+    )]
+    pub allowlist_claim: Option<Box<Account<'info, AllowlistClaim>>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", config.key().as_ref()],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
     /// CHECK: Collection Account that will be checked by the Bubblegum Program
     #[account(
         mut,
@@ -46,6 +107,15 @@ pub struct MintcNFT<'info> {
     /// CHECK: MPL Account Compression Program checked by the corresponding address
     #[account(address = MPL_ACCOUNT_COMPRESSION_ID)]
     pub compression_program: UncheckedAccount<'info>,
+
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub payer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// CHECK: The player's Core asset or token account for the gate, checked in `check_gate`
+    pub gate_account: Option<UncheckedAccount<'info>>,
     pub system_program: Program<'info, System>,
     /// CHECK: This is the ID of the Metaplex Core program
     #[account(address = mpl_core::ID)]
@@ -53,55 +123,172 @@ pub struct MintcNFT<'info> {
 }
 
 impl<'info> MintcNFT<'info> {
-    pub fn mint_cnft(&mut self, name: String, uri: String, symbol: String) -> Result<()> {
+    pub fn check_season(&self) -> Result<()> {
+        active_season(&self.config, self.season.as_deref())?;
+
+        Ok(())
+    }
+
+    pub fn check_drop(&self, allowlist: Option<&AllowlistProof>) -> Result<()> {
+        let Some(mint_drop) = self.mint_drop.as_deref() else {
+            require!(!self.config.drops_only, ErrorCode::DropRequired);
+            return Ok(());
+        };
+
+        require!(
+            mint_drop.is_open(Clock::get()?.unix_timestamp),
+            ErrorCode::DropNotOpen
+        );
+        require!(!mint_drop.is_sold_out(), ErrorCode::DropSoldOut);
+
+        if mint_drop.allowlist_root.is_some() {
+            let (Some(allowlist), Some(allowlist_claim)) =
+                (allowlist, self.allowlist_claim.as_deref())
+            else {
+                return err!(ErrorCode::AllowlistProofMissing);
+            };
+
+            require!(
+                mint_drop.is_allowlisted(&self.player.key(), allowlist.allowance, &allowlist.proof),
+                ErrorCode::NotOnAllowlist
+            );
+            require!(
+                allowlist_claim.claimed < allowlist.allowance,
+                ErrorCode::AllowanceExceeded
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn check_gate(&self) -> Result<()> {
+        check_gate(
+            &self.player.key(),
+            self.config.cnft_gate.as_ref(),
+            self.gate_account.as_deref(),
+        )
+    }
+
+    pub fn authorize(&self) -> Result<()> {
+        authorize_player(
+            &self.player.key(),
+            &self.signer.key(),
+            self.session_token.as_deref(),
+            SESSION_SCOPE_MINT_CNFT,
+        )
+    }
+
+    pub fn pay_fee(&mut self) -> Result<()> {
+        collect_payment(
+            &TokenPaymentAccounts {
+                config: &self.config,
+                payer: &self.payer.to_account_info(),
+                payment_mint: self.payment_mint.as_deref(),
+                payer_token_account: self.payer_token_account.as_deref(),
+                treasury_token_account: self.treasury_token_account.as_deref(),
+                token_program: self.token_program.as_ref(),
+            },
+            &self.treasury.to_account_info(),
+            &self.system_program.to_account_info(),
+            self.config
+                .payment(self.config.cnft_mint_fee, self.config.cnft_token_price),
+        )
+    }
+
+    pub fn mint_cnft(
+        &mut self,
+        name: String,
+        uri: String,
+        symbol: String,
+        delegate_to_config: bool,
+    ) -> Result<()> {
         let seeds = &[
             &b"config"[..],
-            &self.authority.key.as_ref(),
+            self.authority.key.as_ref(),
             &[self.config.bump],
         ];
 
         let signer_seeds = &[&seeds[..]];
 
-        MintV2CpiBuilder::new(&self.bubblegum_program.to_account_info())
-            .tree_config(&self.tree_config.to_account_info())
-            .leaf_owner(&self.player.to_account_info())
-            .leaf_delegate(Some(&self.player))
-            .merkle_tree(&self.merkle_tree.to_account_info())
-            .payer(&self.player.to_account_info())
-            .tree_creator_or_delegate(Some(&self.config.to_account_info()))
-            .core_collection(Some(&self.cnft_collection.to_account_info()))
-            .collection_authority(Some(&self.config.to_account_info()))
-            .mpl_core_cpi_signer(Some(&self.mpl_core_cpi_signer.to_account_info()))
-            .log_wrapper(&self.log_wrapper.to_account_info())
-            .compression_program(&self.compression_program.to_account_info())
-            .system_program(&self.system_program.to_account_info())
-            .mpl_core_program(&self.mpl_core_program.to_account_info())
-            .metadata(MetadataArgsV2 {
-                name,
-                symbol,
-                uri,
-                seller_fee_basis_points: 0,
-                primary_sale_happened: false,
-                is_mutable: false,
-                token_standard: Some(TokenStandard::NonFungible),
-                creators: vec![],
-                collection: Some(self.cnft_collection.key()),
-            })
-            .invoke_signed(signer_seeds)?;
+        let player = &self.player.to_account_info();
+        let config = &self.config.to_account_info();
+        let leaf_delegate =
+            if delegates_to_config(delegate_to_config, &player.key(), &self.signer.key()) {
+                config
+            } else {
+                player
+            };
+
+        let mut metadata = self
+            .config
+            .leaf_metadata(self.config.key(), name, symbol, uri);
+        if let Some(mint_drop) = self.mint_drop.as_deref() {
+            mint_drop.stamp(mint_drop.key(), &mut metadata)?;
+        }
+
+        mint_cnft_leaf(
+            &CnftMintAccounts {
+                bubblegum_program: &self.bubblegum_program.to_account_info(),
+                tree_config: &self.tree_config.to_account_info(),
+                leaf_owner: player,
+                leaf_delegate,
+                merkle_tree: &self.merkle_tree.to_account_info(),
+                payer: &self.payer.to_account_info(),
+                config,
+                cnft_collection: &self.cnft_collection.to_account_info(),
+                mpl_core_cpi_signer: &self.mpl_core_cpi_signer.to_account_info(),
+                log_wrapper: &self.log_wrapper.to_account_info(),
+                compression_program: &self.compression_program.to_account_info(),
+                system_program: &self.system_program.to_account_info(),
+                mpl_core_program: &self.mpl_core_program.to_account_info(),
+            },
+            metadata,
+            signer_seeds,
+        )?;
 
         Ok(())
     }
 
-    pub fn record_mint(&mut self) -> Result<()> {
+    pub fn record_mint(&mut self, bumps: &MintcNFTBumps) -> Result<()> {
         self.config.total_cnfts_minted += 1;
 
+        let Some(mint_drop) = self.mint_drop.as_deref_mut() else {
+            return Ok(());
+        };
+        mint_drop.total_minted += 1;
+
+        if let Some(allowlist_claim) = self.allowlist_claim.as_deref_mut() {
+            if allowlist_claim.bump == 0 {
+                allowlist_claim.set_inner(AllowlistClaim {
+                    mint_drop: mint_drop.key(),
+                    wallet: self.player.key(),
+                    claimed: 0,
+                    bump: bumps.allowlist_claim.unwrap_or_default(),
+                });
+            }
+            allowlist_claim.claimed += 1;
+        }
+
         Ok(())
     }
 }
 
-pub fn handler(ctx: Context<MintcNFT>, name: String, uri: String, symbol: String) -> Result<()> {
-    ctx.accounts.mint_cnft(name, uri, symbol)?;
+pub fn handler(
+    ctx: Context<MintcNFT>,
+    name: String,
+    uri: String,
+    symbol: String,
+    delegate_to_config: bool,
+    allowlist: Option<AllowlistProof>,
+) -> Result<()> {
+    ctx.accounts.authorize()?;
+    ctx.accounts.check_season()?;
+    ctx.accounts.check_gate()?;
+    ctx.accounts.check_drop(allowlist.as_ref())?;
+    ctx.accounts.pay_fee()?;
+    ctx.accounts
+        .mint_cnft(name, uri, symbol, delegate_to_config)?;
 
-    ctx.accounts.record_mint()?;
+    ctx.accounts.record_mint(&ctx.bumps)?;
     Ok(())
 }
@@ -1,16 +1,32 @@
 use anchor_lang::prelude::*;
-use mpl_core::{
-    instructions::CreateV2CpiBuilder,
-    types::{Attribute, Attributes, Plugin, PluginAuthority, PluginAuthorityPair},
-    ID as CORE_PROGRAM_ID,
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use mpl_core::{instructions::CreateV2CpiBuilder, types::Attribute, ID as CORE_PROGRAM_ID};
 
-use crate::{error::ErrorCode, Config, PlayerProgress};
+use crate::{
+    error::ErrorCode,
+    utils::{active_season, authorize_player, check_gate, collect_payment, TokenPaymentAccounts},
+    Config, CraftReceipt, PlayerProgress, Recipe, Season, SessionToken, SESSION_SCOPE_MINT_NFT,
+};
 
 #[derive(Accounts)]
 pub struct MintNFT<'info> {
-    #[account(mut)]
-    pub player: Signer<'info>,
+    pub player: SystemAccount<'info>,
+
+    /// The player, a session key holding a `SessionToken` from the player, or an
+    /// approved crank when the player opted in to auto crafting
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"session", player.key().as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Box<Account<'info, SessionToken>>>,
+
+    #[account(
+        mut,
+        constraint = config.can_pay_for(&payer.key(), &signer.key()) @ ErrorCode::SponsorNotAllowed,
+    )]
+    pub payer: Signer<'info>,
 
     pub authority: SystemAccount<'info>,
 
@@ -18,15 +34,27 @@ pub struct MintNFT<'info> {
         mut,
         seeds = [b"config", authority.key().as_ref()],
         bump = config.bump,
+        constraint = config.has_supply_left() @ ErrorCode::MaxSupplyReached,
     )]
     pub config: Box<Account<'info, Config>>,
 
+    #[account(
+        seeds = [b"season", config.key().as_ref(), &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", config.key().as_ref()],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
     #[account(
         mut,
         seeds = [b"player", player.key().as_ref()],
         bump = player_progress.bump,
-        constraint = player_progress.total_cnfts_burned == 5 @ ErrorCode::NotEnoughBurns
-
     )]
     pub player_progress: Box<Account<'info, PlayerProgress>>,
 
@@ -38,72 +66,202 @@ pub struct MintNFT<'info> {
     )]
     pub nft_collection: UncheckedAccount<'info>,
 
+    /// CHECK: Either a fresh keypair signing the transaction, or the PDA derived from
+    /// `[b"asset", config, player, craft_count]`, checked in `mint_nft`
     #[account(
         mut,
         constraint = asset.data_is_empty() @ ErrorCode::AssetAlreadyInitialized
     )]
-    pub asset: Signer<'info>,
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"receipt", asset.key().as_ref()],
+        bump,
+        space = CraftReceipt::DISCRIMINATOR.len() + CraftReceipt::INIT_SPACE,
+    )]
+    pub craft_receipt: Box<Account<'info, CraftReceipt>>,
 
     #[account(address = CORE_PROGRAM_ID)]
     /// CHECK: This will also be checked by core
     pub core_program: UncheckedAccount<'info>,
+
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub payer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// CHECK: The player's Core asset or token account for the gate, checked in `check_gate`
+    pub gate_account: Option<UncheckedAccount<'info>>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> MintNFT<'info> {
+    pub fn check_season(&mut self) -> Result<()> {
+        let season = active_season(&self.config, self.season.as_deref())?;
+        self.player_progress.sync_season(season);
+
+        require!(
+            self.player_progress.total_cnfts_burned >= self.craft_cost(),
+            ErrorCode::NotEnoughBurns
+        );
+
+        Ok(())
+    }
+
+    pub fn craft_cost(&self) -> u32 {
+        self.config
+            .craft_cost(self.season.as_deref().map(|season| &**season))
+    }
+
+    pub fn check_gate(&self) -> Result<()> {
+        check_gate(
+            &self.player.key(),
+            self.config.nft_gate.as_ref(),
+            self.gate_account.as_deref(),
+        )
+    }
+
+    pub fn authorize(&self) -> Result<()> {
+        if self
+            .player_progress
+            .allows_crank(&self.config, &self.signer.key())
+        {
+            return Ok(());
+        }
+
+        authorize_player(
+            &self.player.key(),
+            &self.signer.key(),
+            self.session_token.as_deref(),
+            SESSION_SCOPE_MINT_NFT,
+        )
+    }
+
+    pub fn pay_fee(&mut self) -> Result<()> {
+        collect_payment(
+            &TokenPaymentAccounts {
+                config: &self.config,
+                payer: &self.payer.to_account_info(),
+                payment_mint: self.payment_mint.as_deref(),
+                payer_token_account: self.payer_token_account.as_deref(),
+                treasury_token_account: self.treasury_token_account.as_deref(),
+                token_program: self.token_program.as_ref(),
+            },
+            &self.treasury.to_account_info(),
+            &self.system_program.to_account_info(),
+            self.config
+                .payment(self.config.nft_mint_fee, self.config.nft_token_price),
+        )
+    }
+
     pub fn mint_nft(&mut self, name: String, uri: String) -> Result<()> {
         let seeds = &[
             &b"config"[..],
-            &self.authority.key.as_ref(),
+            self.authority.key.as_ref(),
             &[self.config.bump],
         ];
 
-        let signer_seeds = &[&seeds[..]];
+        let config_key = self.config.key();
+        let player_key = self.player.key();
+        let craft_index = self.player_progress.craft_count.to_le_bytes();
+        let mut asset_seeds: Vec<&[u8]> = vec![
+            b"asset",
+            config_key.as_ref(),
+            player_key.as_ref(),
+            &craft_index,
+        ];
+
+        let asset_bump;
+        let signer_seeds: &[&[&[u8]]] = if self.asset.is_signer {
+            &[&seeds[..]]
+        } else {
+            let (asset, bump) = self.player_progress.next_asset_address(&config_key);
+            require_keys_eq!(self.asset.key(), asset, ErrorCode::InvalidAssetAddress);
+
+            asset_bump = [bump];
+            asset_seeds.push(&asset_bump);
+            &[&seeds[..], &asset_seeds[..]]
+        };
+
+        let plugins = self.config.asset_plugins(
+            config_key,
+            self.config.total_editions + 1,
+            vec![
+                Attribute {
+                    key: "Player".to_string(),
+                    value: self.player.key().to_string(),
+                },
+                Attribute {
+                    key: "Collection".to_string(),
+                    value: self.nft_collection.key().to_string(),
+                },
+                Attribute {
+                    key: "Level".to_string(),
+                    value: "1".to_string(),
+                },
+                Attribute {
+                    key: "XP".to_string(),
+                    value: "0".to_string(),
+                },
+            ],
+        );
 
         CreateV2CpiBuilder::new(&self.core_program.to_account_info())
             .asset(&self.asset.to_account_info())
             .collection(Some(&self.nft_collection.to_account_info()))
             .authority(Some(&self.config.to_account_info()))
-            .payer(&self.player.to_account_info())
+            .payer(&self.payer.to_account_info())
             .owner(Some(&self.player.to_account_info()))
             .update_authority(None)
             .system_program(&self.system_program.to_account_info())
             .name(name)
             .uri(uri)
-            .plugins(vec![PluginAuthorityPair {
-                plugin: Plugin::Attributes(Attributes {
-                    attribute_list: vec![
-                        Attribute {
-                            key: "Player".to_string(),
-                            value: self.player.key().to_string(),
-                        },
-                        Attribute {
-                            key: "Collection".to_string(),
-                            value: self.nft_collection.key().to_string(),
-                        },
-                    ],
-                }),
-                authority: Some(PluginAuthority::Address {
-                    address: self.config.key(),
-                }),
-            }])
+            .plugins(plugins)
             .external_plugin_adapters(vec![])
             .invoke_signed(signer_seeds)?;
 
         Ok(())
     }
 
+    pub fn create_receipt(&mut self, bumps: &MintNFTBumps) -> Result<()> {
+        let cnfts_burned = self.craft_cost();
+        let burned_leaves_hash = self.player_progress.spend_burns(cnfts_burned)?;
+
+        self.craft_receipt.set_inner(CraftReceipt {
+            player: self.player.key(),
+            asset: self.asset.key(),
+            recipe: Recipe::Burn {
+                season_id: self.player_progress.season_id,
+            },
+            cnfts_burned,
+            slot: Clock::get()?.slot,
+            burned_leaves_hash,
+            rent_payer: self.payer.key(),
+            bump: bumps.craft_receipt,
+        });
+
+        Ok(())
+    }
+
     pub fn record_mint(&mut self) -> Result<()> {
-        self.config.total_nfts_minted += 1;
+        self.config.record_craft(&mut self.player_progress);
 
-        self.player_progress.total_cnfts_burned = 0;
-        self.player_progress.total_nfts_minted += 1;
+        self.player_progress.record_nft_mint();
+        self.player_progress.craft_count += 1;
         Ok(())
     }
 }
 
 pub fn handler(ctx: Context<MintNFT>, name: String, uri: String) -> Result<()> {
+    ctx.accounts.authorize()?;
+    ctx.accounts.check_season()?;
+    ctx.accounts.check_gate()?;
+    ctx.accounts.pay_fee()?;
     ctx.accounts.mint_nft(name, uri)?;
+    ctx.accounts.create_receipt(&ctx.bumps)?;
 
     ctx.accounts.record_mint()?;
     Ok(())
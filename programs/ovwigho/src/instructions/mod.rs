@@ -1,9 +1,73 @@
+pub mod add_cnft_template;
+pub mod add_tier;
+pub mod admin_burn_nft;
+pub mod admin_freeze_nft;
+pub mod airdrop_cnfts;
+pub mod auto_burn_cnft;
 pub mod burn_cnft;
+pub mod burn_nft;
+pub mod close_craft_receipt;
+pub mod create_drop;
+pub mod create_guild;
+pub mod create_session;
+pub mod decompose_nft;
+pub mod enable_moderation;
+pub mod end_season;
+pub mod guild_craft;
 pub mod initialize;
+pub mod level_up_nft;
+pub mod merge_nfts;
+pub mod migrate_config;
 pub mod mint_cnft;
 pub mod mint_nft;
+pub mod revoke_session;
+pub mod set_auto_craft;
+pub mod set_contributors;
+pub mod set_gates;
+pub mod set_token_payment;
+pub mod start_season;
+pub mod thaw_nft;
+pub mod update_cnft_metadata;
+pub mod update_collection;
+pub mod update_config;
+pub mod update_guild;
+pub mod update_royalties;
+pub mod withdraw_treasury;
+pub mod withdraw_treasury_tokens;
 
+pub use add_cnft_template::*;
+pub use add_tier::*;
+pub use admin_burn_nft::*;
+pub use admin_freeze_nft::*;
+pub use airdrop_cnfts::*;
+pub use auto_burn_cnft::*;
 pub use burn_cnft::*;
+pub use burn_nft::*;
+pub use close_craft_receipt::*;
+pub use create_drop::*;
+pub use create_guild::*;
+pub use create_session::*;
+pub use decompose_nft::*;
+pub use enable_moderation::*;
+pub use end_season::*;
+pub use guild_craft::*;
 pub use initialize::*;
+pub use level_up_nft::*;
+pub use merge_nfts::*;
+pub use migrate_config::*;
 pub use mint_cnft::*;
 pub use mint_nft::*;
+pub use revoke_session::*;
+pub use set_auto_craft::*;
+pub use set_contributors::*;
+pub use set_gates::*;
+pub use set_token_payment::*;
+pub use start_season::*;
+pub use thaw_nft::*;
+pub use update_cnft_metadata::*;
+pub use update_collection::*;
+pub use update_config::*;
+pub use update_guild::*;
+pub use update_royalties::*;
+pub use withdraw_treasury::*;
+pub use withdraw_treasury_tokens::*;
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::SessionToken;
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        has_one = player,
+        seeds = [b"session", player.key().as_ref(), session_token.session_key.as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Box<Account<'info, SessionToken>>,
+}
+
+pub fn handler(_ctx: Context<RevokeSession>) -> Result<()> {
+    Ok(())
+}
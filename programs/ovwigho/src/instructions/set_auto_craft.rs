@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, PlayerProgress};
+
+#[derive(Accounts)]
+pub struct SetAutoCraft<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        seeds = [b"player", player.key().as_ref()],
+        bump,
+        space = PlayerProgress::DISCRIMINATOR.len() + PlayerProgress::INIT_SPACE,
+    )]
+    pub player_progress: Box<Account<'info, PlayerProgress>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetAutoCraft<'info> {
+    pub fn init_player_progress(&mut self, bumps: &SetAutoCraftBumps) -> Result<()> {
+        if self.player_progress.bump == 0 {
+            self.player_progress.set_inner(PlayerProgress {
+                player: self.player.key(),
+                authority: self.authority.key(),
+                bump: bumps.player_progress,
+                ..Default::default()
+            });
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetAutoCraft>, enabled: bool) -> Result<()> {
+    ctx.accounts.init_player_progress(&ctx.bumps)?;
+    ctx.accounts.player_progress.auto_craft = enabled;
+
+    Ok(())
+}
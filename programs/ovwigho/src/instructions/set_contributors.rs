@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, Config, PlayerProgress, MAX_CONTRIBUTORS};
+
+/// Controls who may burn cNFTs toward the player's crafts: anyone when
+/// `open` is set, otherwise only the listed contributors.
+#[derive(Accounts)]
+pub struct SetContributors<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        seeds = [b"player", player.key().as_ref()],
+        bump,
+        space = PlayerProgress::DISCRIMINATOR.len() + PlayerProgress::INIT_SPACE,
+    )]
+    pub player_progress: Box<Account<'info, PlayerProgress>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetContributors<'info> {
+    pub fn init_player_progress(&mut self, bumps: &SetContributorsBumps) -> Result<()> {
+        if self.player_progress.bump == 0 {
+            self.player_progress.set_inner(PlayerProgress {
+                player: self.player.key(),
+                authority: self.authority.key(),
+                bump: bumps.player_progress,
+                ..Default::default()
+            });
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetContributors>, open: bool, contributors: Vec<Pubkey>) -> Result<()> {
+    require!(
+        contributors.len() <= MAX_CONTRIBUTORS,
+        ErrorCode::TooManyContributors
+    );
+
+    ctx.accounts.init_player_progress(&ctx.bumps)?;
+    ctx.accounts.player_progress.open_contributions = open;
+    ctx.accounts.player_progress.contributors = contributors;
+
+    Ok(())
+}
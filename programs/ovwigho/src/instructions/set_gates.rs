@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, Gate};
+
+/// Replaces the holding requirements for minting cNFTs and crafting NFTs.
+/// `None` removes the gate.
+#[derive(Accounts)]
+pub struct SetGates<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+pub fn handler(
+    ctx: Context<SetGates>,
+    cnft_gate: Option<Gate>,
+    nft_gate: Option<Gate>,
+) -> Result<()> {
+    ctx.accounts.config.cnft_gate = cnft_gate;
+    ctx.accounts.config.nft_gate = nft_gate;
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{error::ErrorCode, Config};
+
+/// Sets the SPL token charged for minting and crafting, creating the
+/// config-owned treasury token account if needed. While a mint is set every
+/// action is paid in tokens, and a price of zero makes that action free.
+/// Passing no mint switches back to the SOL fees.
+#[derive(Accounts)]
+pub struct SetTokenPayment<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mint::token_program = token_program)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = payment_mint,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetTokenPayment>, cnft_price: u64, nft_price: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    require!(
+        accounts.payment_mint.is_some() == accounts.treasury_token_account.is_some(),
+        ErrorCode::PaymentAccountsMissing
+    );
+
+    let payment_mint = accounts.payment_mint.as_ref().map(|mint| mint.key());
+    accounts
+        .config
+        .set_token_payment(payment_mint, cnft_price, nft_price);
+
+    Ok(())
+}
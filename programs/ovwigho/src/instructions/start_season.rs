@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, Config, Season};
+
+/// Creates the next season and makes it the active one. Player progress from
+/// earlier seasons is reset lazily the next time it's touched.
+#[derive(Accounts)]
+pub struct StartSeason<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            b"season",
+            config.key().as_ref(),
+            &(config.season_count + 1).to_le_bytes(),
+        ],
+        bump,
+        space = Season::DISCRIMINATOR.len() + Season::INIT_SPACE,
+    )]
+    pub season: Box<Account<'info, Season>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StartSeason<'info> {
+    pub fn start_season(
+        &mut self,
+        start_ts: i64,
+        end_ts: i64,
+        cnfts_per_craft: u32,
+        bumps: &StartSeasonBumps,
+    ) -> Result<()> {
+        require!(start_ts < end_ts, ErrorCode::InvalidSeasonWindow);
+        require!(cnfts_per_craft > 0, ErrorCode::InvalidCraftCost);
+
+        self.config.season_count += 1;
+        self.config.active_season = Some(self.config.season_count);
+
+        self.season.set_inner(Season {
+            config: self.config.key(),
+            id: self.config.season_count,
+            start_ts,
+            end_ts,
+            cnfts_per_craft,
+            bump: bumps.season,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<StartSeason>,
+    start_ts: i64,
+    end_ts: i64,
+    cnfts_per_craft: u32,
+) -> Result<()> {
+    ctx.accounts
+        .start_season(start_ts, end_ts, cnfts_per_craft, &ctx.bumps)?;
+
+    Ok(())
+}
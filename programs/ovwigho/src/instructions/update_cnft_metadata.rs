@@ -0,0 +1,257 @@
+use anchor_lang::prelude::*;
+use mpl_account_compression::ID as MPL_ACCOUNT_COMPRESSION_ID;
+use mpl_bubblegum::instructions::UpdateMetadataV2CpiBuilder;
+use mpl_bubblegum::types::{Creator, MetadataArgsV2, TokenStandard, UpdateArgs};
+use mpl_bubblegum::ID as BUBBLEGUM_ID;
+use mpl_noop::ID as MPL_NOOP_ID;
+
+use crate::{error::ErrorCode, Config};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LeafCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CnftMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub creators: Vec<LeafCreator>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CnftMetadataUpdate {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub is_mutable: Option<bool>,
+}
+
+impl CnftMetadata {
+    pub fn into_leaf(self, collection: Pubkey) -> MetadataArgsV2 {
+        MetadataArgsV2 {
+            name: self.name,
+            symbol: self.symbol,
+            uri: self.uri,
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            primary_sale_happened: self.primary_sale_happened,
+            is_mutable: self.is_mutable,
+            token_standard: Some(TokenStandard::NonFungible),
+            creators: self
+                .creators
+                .into_iter()
+                .map(|creator| Creator {
+                    address: creator.address,
+                    verified: creator.verified,
+                    share: creator.share,
+                })
+                .collect(),
+            collection: Some(collection),
+        }
+    }
+}
+
+impl From<CnftMetadataUpdate> for UpdateArgs {
+    fn from(update: CnftMetadataUpdate) -> Self {
+        UpdateArgs {
+            name: update.name,
+            symbol: update.symbol,
+            uri: update.uri,
+            creators: None,
+            seller_fee_basis_points: None,
+            primary_sale_happened: None,
+            is_mutable: update.is_mutable,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateCnftMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Collection Account that will be checked by the Bubblegum Program
+    #[account(
+        mut,
+        constraint = !cnft_collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+        address = config.cnft_collection,
+    )]
+    pub cnft_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Leaf owner that will be checked against the leaf by the Bubblegum Program
+    pub leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: Leaf delegate that will be checked against the leaf by the Bubblegum Program
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: Tree Config checks will be performed by the Bubblegum Program
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: Merkle Tree account that will be checked by the Bubblegum Program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: MPL NOOP Program checked by the corresponding address
+    #[account(address = MPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: Bubblegum Program checked by the corresponding address
+    #[account(address = BUBBLEGUM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: MPL Account Compression Program checked by the corresponding address
+    #[account(address = MPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateCnftMetadata<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_cnft_metadata(
+        &self,
+        root: [u8; 32],
+        nonce: u64,
+        index: u32,
+        current_metadata: CnftMetadata,
+        update_args: CnftMetadataUpdate,
+        asset_data_hash: Option<[u8; 32]>,
+        flags: Option<u8>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(current_metadata.is_mutable, ErrorCode::MetadataImmutable);
+
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let bubblegum_program = &self.bubblegum_program.to_account_info();
+        let tree_config = &self.tree_config.to_account_info();
+        let config = &self.config.to_account_info();
+        let payer = &self.authority.to_account_info();
+        let leaf_owner = &self.leaf_owner.to_account_info();
+        let leaf_delegate = &self.leaf_delegate.to_account_info();
+        let merkle_tree = &self.merkle_tree.to_account_info();
+        let cnft_collection = &self.cnft_collection.to_account_info();
+        let log_wrapper = &self.log_wrapper.to_account_info();
+        let compression_program = &self.compression_program.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+
+        let mut builder = UpdateMetadataV2CpiBuilder::new(bubblegum_program);
+
+        builder
+            .tree_config(tree_config)
+            .payer(payer)
+            .authority(Some(config))
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(Some(leaf_delegate))
+            .merkle_tree(merkle_tree)
+            .core_collection(Some(cnft_collection))
+            .log_wrapper(log_wrapper)
+            .compression_program(compression_program)
+            .system_program(system_program)
+            .root(root)
+            .nonce(nonce)
+            .index(index)
+            .current_metadata(current_metadata.into_leaf(self.cnft_collection.key()))
+            .update_args(update_args.into());
+
+        if let Some(asset_data_hash) = asset_data_hash {
+            builder.asset_data_hash(asset_data_hash);
+        }
+
+        if let Some(flags) = flags {
+            builder.flags(flags);
+        }
+
+        for account in remaining_accounts {
+            builder.add_remaining_accounts(&[(account, false, false)]);
+        }
+
+        builder.invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateCnftMetadata<'info>>,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32,
+    current_metadata: CnftMetadata,
+    update_args: CnftMetadataUpdate,
+    asset_data_hash: Option<[u8; 32]>,
+    flags: Option<u8>,
+) -> Result<()> {
+    ctx.accounts.update_cnft_metadata(
+        root,
+        nonce,
+        index,
+        current_metadata,
+        update_args,
+        asset_data_hash,
+        flags,
+        ctx.remaining_accounts,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current_metadata_rebuilds_the_minted_leaf() {
+        let collection = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let leaf = CnftMetadata {
+            name: "a".into(),
+            symbol: "A".into(),
+            uri: "uri".into(),
+            seller_fee_basis_points: 250,
+            primary_sale_happened: false,
+            is_mutable: true,
+            creators: vec![LeafCreator {
+                address: creator,
+                verified: true,
+                share: 100,
+            }],
+        }
+        .into_leaf(collection);
+
+        assert_eq!(leaf.collection, Some(collection));
+        assert_eq!(leaf.token_standard, Some(TokenStandard::NonFungible));
+        assert_eq!(leaf.seller_fee_basis_points, 250);
+        assert_eq!(
+            leaf.creators,
+            vec![Creator {
+                address: creator,
+                verified: true,
+                share: 100,
+            }]
+        );
+
+        let update: UpdateArgs = CnftMetadataUpdate {
+            name: None,
+            symbol: None,
+            uri: Some("fixed".into()),
+            is_mutable: Some(false),
+        }
+        .into();
+        assert_eq!(update.uri.as_deref(), Some("fixed"));
+        assert_eq!(update.is_mutable, Some(false));
+        assert!(update.name.is_none() && update.creators.is_none());
+    }
+}
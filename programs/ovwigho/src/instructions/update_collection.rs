@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::UpdateCollectionV1CpiBuilder,
+    types::{Attribute, Attributes, MasterEdition, Plugin},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{error::ErrorCode, utils::upsert_collection_plugin, Config, RoyaltyArgs};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AttributeArgs {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CollectionPluginArgs {
+    Royalties(RoyaltyArgs),
+    MasterEdition {
+        max_supply: Option<u32>,
+        name: Option<String>,
+        uri: Option<String>,
+    },
+    Attributes(Vec<AttributeArgs>),
+}
+
+impl CollectionPluginArgs {
+    /// Builds the Core plugin and mirrors the settings the program also
+    /// tracks on the config.
+    pub fn apply(self, config: &mut Config, is_cnft_collection: bool) -> Result<Plugin> {
+        Ok(match self {
+            CollectionPluginArgs::Royalties(royalties) => {
+                if is_cnft_collection {
+                    config.cnft_seller_fee_basis_points = royalties.basis_points;
+                }
+                Plugin::Royalties(royalties.into())
+            }
+            CollectionPluginArgs::MasterEdition {
+                max_supply,
+                name,
+                uri,
+            } => {
+                require!(!is_cnft_collection, ErrorCode::InvalidCollection);
+                config.nft_max_supply = max_supply;
+                Plugin::MasterEdition(MasterEdition {
+                    max_supply,
+                    name,
+                    uri,
+                })
+            }
+            CollectionPluginArgs::Attributes(attributes) => Plugin::Attributes(Attributes {
+                attribute_list: attributes
+                    .into_iter()
+                    .map(|attribute| Attribute {
+                        key: attribute.key,
+                        value: attribute.value,
+                    })
+                    .collect(),
+            }),
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateCollectionArgs {
+    pub name: Option<String>,
+    pub uri: Option<String>,
+    pub plugins: Vec<CollectionPluginArgs>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCollection<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Either the cNFT or the NFT collection of this config, checked against the config
+    #[account(
+        mut,
+        constraint = collection.key() == config.cnft_collection
+            || collection.key() == config.nft_collection @ ErrorCode::InvalidCollection,
+        constraint = !collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateCollection<'info> {
+    pub fn update_collection(&mut self, name: Option<String>, uri: Option<String>) -> Result<()> {
+        if name.is_none() && uri.is_none() {
+            return Ok(());
+        }
+
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let core_program = &self.core_program.to_account_info();
+        let collection = &self.collection.to_account_info();
+        let payer = &self.authority.to_account_info();
+        let authority = &self.config.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+
+        let mut builder = UpdateCollectionV1CpiBuilder::new(core_program);
+
+        builder
+            .collection(collection)
+            .payer(payer)
+            .authority(Some(authority))
+            .system_program(system_program);
+
+        if let Some(name) = name {
+            builder.new_name(name);
+        }
+        if let Some(uri) = uri {
+            builder.new_uri(uri);
+        }
+
+        builder.invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    pub fn update_plugin(&mut self, plugin: CollectionPluginArgs) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let is_cnft_collection = self.collection.key() == self.config.cnft_collection;
+
+        let plugin = plugin.apply(&mut self.config, is_cnft_collection)?;
+
+        upsert_collection_plugin(
+            &self.core_program.to_account_info(),
+            &self.collection.to_account_info(),
+            &self.authority.to_account_info(),
+            &self.config.to_account_info(),
+            &self.system_program.to_account_info(),
+            plugin,
+            signer_seeds,
+        )?;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateCollection>, args: UpdateCollectionArgs) -> Result<()> {
+    ctx.accounts.update_collection(args.name, args.uri)?;
+
+    for plugin in args.plugins {
+        ctx.accounts.update_plugin(plugin)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::config::tests::test_config;
+
+    #[test]
+    fn master_edition_only_applies_to_the_nft_collection() {
+        let mut config = test_config(Pubkey::new_unique());
+        let edition = || CollectionPluginArgs::MasterEdition {
+            max_supply: Some(50),
+            name: None,
+            uri: None,
+        };
+
+        assert!(edition().apply(&mut config, true).is_err());
+        assert_eq!(config.nft_max_supply, None);
+
+        let plugin = edition().apply(&mut config, false).unwrap();
+        assert!(matches!(
+            plugin,
+            Plugin::MasterEdition(MasterEdition {
+                max_supply: Some(50),
+                ..
+            })
+        ));
+        assert_eq!(config.nft_max_supply, Some(50));
+    }
+
+    #[test]
+    fn royalties_mirror_the_cnft_seller_fee_only() {
+        let mut config = test_config(Pubkey::new_unique());
+        let royalties = |basis_points| {
+            CollectionPluginArgs::Royalties(RoyaltyArgs {
+                basis_points,
+                creators: vec![],
+                rule_set: crate::RoyaltyRuleSet::None,
+            })
+        };
+
+        let plugin = royalties(300).apply(&mut config, false).unwrap();
+        assert!(matches!(plugin, Plugin::Royalties(royalties) if royalties.basis_points == 300));
+        assert_eq!(config.cnft_seller_fee_basis_points, 0);
+
+        royalties(700).apply(&mut config, true).unwrap();
+        assert_eq!(config.cnft_seller_fee_basis_points, 700);
+    }
+}
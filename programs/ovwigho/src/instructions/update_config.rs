@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode, CnftCreator, Config, RevenueShare, MAX_CRANKS, MAX_REVENUE_SHARES,
+    MAX_SPONSORS, MAX_URI_PREFIX_LEN,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateConfigArgs {
+    pub cnft_creators: Option<Vec<CnftCreator>>,
+    pub cnft_is_mutable: Option<bool>,
+    pub soulbound: Option<bool>,
+    pub burn_reward_points: Option<u32>,
+    pub cnfts_per_craft: Option<u32>,
+    pub decompose_loss_bps: Option<u16>,
+    pub level_up_points: Option<u32>,
+    pub level_up_burns: Option<u32>,
+    pub level_uri_prefix: Option<String>,
+    pub cnft_mint_fee: Option<u64>,
+    pub nft_mint_fee: Option<u64>,
+    pub revenue_shares: Option<Vec<RevenueShare>>,
+    pub sponsors: Option<Vec<Pubkey>>,
+    pub cranks: Option<Vec<Pubkey>>,
+    pub drops_only: Option<bool>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_cnft_creators(&mut self, creators: Vec<CnftCreator>) -> Result<()> {
+        require!(
+            creators.len() <= self.config.max_cnft_creators(),
+            ErrorCode::TooManyCreators
+        );
+        require!(
+            creators.iter().map(|c| c.share as u16).sum::<u16>() == 100,
+            ErrorCode::InvalidCreatorShares
+        );
+        require!(
+            creators.iter().any(|c| c.address == self.config.key()),
+            ErrorCode::ConfigCreatorMissing
+        );
+
+        self.config.cnft_creators = creators;
+
+        Ok(())
+    }
+
+    pub fn update_revenue_shares(&mut self, shares: Vec<RevenueShare>) -> Result<()> {
+        require!(
+            shares.len() <= MAX_REVENUE_SHARES,
+            ErrorCode::TooManyRevenueShares
+        );
+        require!(
+            shares.is_empty()
+                || shares.iter().map(|s| s.basis_points as u32).sum::<u32>() == 10_000,
+            ErrorCode::InvalidRevenueShares
+        );
+
+        self.config.revenue_shares = shares;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
+    if let Some(creators) = args.cnft_creators {
+        ctx.accounts.update_cnft_creators(creators)?;
+    }
+
+    if let Some(is_mutable) = args.cnft_is_mutable {
+        ctx.accounts.config.cnft_is_mutable = is_mutable;
+    }
+
+    if let Some(soulbound) = args.soulbound {
+        ctx.accounts.config.soulbound = soulbound;
+    }
+
+    if let Some(points) = args.burn_reward_points {
+        ctx.accounts.config.burn_reward_points = points;
+    }
+
+    if let Some(cnfts_per_craft) = args.cnfts_per_craft {
+        require!(cnfts_per_craft > 0, ErrorCode::InvalidCraftCost);
+        ctx.accounts.config.cnfts_per_craft = cnfts_per_craft;
+    }
+
+    if let Some(loss_bps) = args.decompose_loss_bps {
+        require!(loss_bps <= 10_000, ErrorCode::InvalidBasisPoints);
+        ctx.accounts.config.decompose_loss_bps = loss_bps;
+    }
+
+    if let Some(points) = args.level_up_points {
+        ctx.accounts.config.level_up_points = points;
+    }
+
+    if let Some(burns) = args.level_up_burns {
+        ctx.accounts.config.level_up_burns = burns;
+    }
+
+    if let Some(prefix) = args.level_uri_prefix {
+        require!(
+            prefix.len() <= MAX_URI_PREFIX_LEN,
+            ErrorCode::UriPrefixTooLong
+        );
+        ctx.accounts.config.level_uri_prefix = prefix;
+    }
+
+    if let Some(fee) = args.cnft_mint_fee {
+        ctx.accounts.config.cnft_mint_fee = fee;
+    }
+
+    if let Some(fee) = args.nft_mint_fee {
+        ctx.accounts.config.nft_mint_fee = fee;
+    }
+
+    if let Some(shares) = args.revenue_shares {
+        ctx.accounts.update_revenue_shares(shares)?;
+    }
+
+    if let Some(sponsors) = args.sponsors {
+        require!(sponsors.len() <= MAX_SPONSORS, ErrorCode::TooManySponsors);
+        ctx.accounts.config.sponsors = sponsors;
+    }
+
+    if let Some(cranks) = args.cranks {
+        require!(cranks.len() <= MAX_CRANKS, ErrorCode::TooManyCranks);
+        ctx.accounts.config.cranks = cranks;
+    }
+
+    if let Some(drops_only) = args.drops_only {
+        ctx.accounts.config.drops_only = drops_only;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, utils::signer_keys, Guild, MAX_GUILD_MEMBERS};
+
+/// Members being added sign the transaction, passed as remaining accounts.
+#[derive(Accounts)]
+pub struct UpdateGuild<'info> {
+    pub leader: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = leader,
+        seeds = [b"guild", guild.config.as_ref(), leader.key().as_ref()],
+        bump = guild.bump,
+    )]
+    pub guild: Box<Account<'info, Guild>>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateGuild>,
+    members: Option<Vec<Pubkey>>,
+    cnfts_per_craft: Option<u32>,
+) -> Result<()> {
+    if let Some(members) = members {
+        require!(
+            members.len() <= MAX_GUILD_MEMBERS,
+            ErrorCode::TooManyGuildMembers
+        );
+        Guild::check_consent(
+            &ctx.accounts.guild.members,
+            &members,
+            &signer_keys(ctx.remaining_accounts),
+        )?;
+        ctx.accounts.guild.members = members;
+    }
+
+    if let Some(cnfts_per_craft) = cnfts_per_craft {
+        require!(cnfts_per_craft > 0, ErrorCode::InvalidCraftCost);
+        ctx.accounts.guild.cnfts_per_craft = cnfts_per_craft;
+    }
+
+    Ok(())
+}
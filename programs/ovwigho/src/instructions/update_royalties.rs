@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    error::ErrorCode, utils::upsert_collection_plugin, CollectionPluginArgs, Config, RoyaltyArgs,
+};
+
+/// Shorthand for an `update_collection` that only sets the Royalties plugin.
+#[derive(Accounts)]
+pub struct UpdateRoyalties<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Either the cNFT or the NFT collection of this config, checked against the config
+    #[account(
+        mut,
+        constraint = collection.key() == config.cnft_collection
+            || collection.key() == config.nft_collection @ ErrorCode::InvalidCollection,
+        constraint = !collection.data_is_empty() @ ErrorCode::CollectionNotInitialized,
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: This will also be checked by core
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateRoyalties<'info> {
+    pub fn update_royalties(&mut self, royalties: RoyaltyArgs) -> Result<()> {
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let is_cnft_collection = self.collection.key() == self.config.cnft_collection;
+
+        let plugin = CollectionPluginArgs::Royalties(royalties)
+            .apply(&mut self.config, is_cnft_collection)?;
+
+        upsert_collection_plugin(
+            &self.core_program.to_account_info(),
+            &self.collection.to_account_info(),
+            &self.authority.to_account_info(),
+            &self.config.to_account_info(),
+            &self.system_program.to_account_info(),
+            plugin,
+            signer_seeds,
+        )?;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateRoyalties>, royalties: RoyaltyArgs) -> Result<()> {
+    ctx.accounts.update_royalties(royalties)?;
+    Ok(())
+}
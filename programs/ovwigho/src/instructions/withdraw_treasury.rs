@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{error::ErrorCode, Config};
+
+/// Withdraws `amount` lamports from the treasury. When revenue shares are
+/// configured, the recipients are passed as remaining accounts in the same
+/// order and any rounding dust goes to the authority.
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", config.key().as_ref()],
+        bump = config.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawTreasury<'info> {
+    pub fn pay(&self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let config = self.config.key();
+        let seeds = &[
+            &b"treasury"[..],
+            config.as_ref(),
+            &[self.config.treasury_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: recipient.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+
+    pub fn withdraw(&self, amount: u64, recipients: &[AccountInfo<'info>]) -> Result<()> {
+        let available = self
+            .treasury
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        require!(amount <= available, ErrorCode::InsufficientTreasuryBalance);
+
+        let shares = &self.config.revenue_shares;
+        require!(
+            recipients.len() == shares.len(),
+            ErrorCode::RevenueShareRecipientMismatch
+        );
+
+        let cuts = self.config.revenue_cuts(amount);
+
+        let mut paid = 0;
+        for ((share, recipient), cut) in shares.iter().zip(recipients).zip(cuts) {
+            require_keys_eq!(
+                recipient.key(),
+                share.recipient,
+                ErrorCode::RevenueShareRecipientMismatch
+            );
+
+            self.pay(recipient, cut)?;
+            paid += cut;
+        }
+
+        self.pay(&self.authority.to_account_info(), amount - paid)?;
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawTreasury<'info>>,
+    amount: u64,
+) -> Result<()> {
+    ctx.accounts.withdraw(amount, ctx.remaining_accounts)?;
+    Ok(())
+}
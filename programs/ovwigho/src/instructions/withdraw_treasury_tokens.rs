@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{error::ErrorCode, Config};
+
+/// Withdraws `amount` payment tokens from the config-owned treasury token
+/// account, split like `withdraw_treasury`: each revenue share recipient's token
+/// account for the mint is passed as a remaining account in the same order, and
+/// the rest, including rounding dust, goes to `destination`.
+#[derive(Accounts)]
+pub struct WithdrawTreasuryTokens<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", authority.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mint::token_program = token_program)]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::token_program = token_program,
+    )]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawTreasuryTokens<'info> {
+    pub fn pay(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let seeds = &[
+            &b"config"[..],
+            self.authority.key.as_ref(),
+            &[self.config.bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.treasury_token_account.to_account_info(),
+                    mint: self.payment_mint.to_account_info(),
+                    to,
+                    authority: self.config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.payment_mint.decimals,
+        )
+    }
+
+    pub fn withdraw(&self, amount: u64, recipients: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            amount <= self.treasury_token_account.amount,
+            ErrorCode::InsufficientTreasuryBalance
+        );
+
+        let shares = &self.config.revenue_shares;
+        require!(
+            recipients.len() == shares.len(),
+            ErrorCode::RevenueShareRecipientMismatch
+        );
+
+        let cuts = self.config.revenue_cuts(amount);
+
+        let mut paid = 0;
+        for ((share, recipient), cut) in shares.iter().zip(recipients).zip(cuts) {
+            let token_account = InterfaceAccount::<TokenAccount>::try_from(recipient)?;
+            require!(
+                pays_share(&token_account, &share.recipient, &self.payment_mint.key()),
+                ErrorCode::RevenueShareRecipientMismatch
+            );
+
+            self.pay(recipient.clone(), cut)?;
+            paid += cut;
+        }
+
+        self.pay(self.destination.to_account_info(), amount - paid)?;
+
+        Ok(())
+    }
+}
+
+/// A revenue share is paid into a token account its recipient owns for the
+/// payment mint.
+pub fn pays_share(token_account: &TokenAccount, recipient: &Pubkey, payment_mint: &Pubkey) -> bool {
+    token_account.owner == *recipient && token_account.mint == *payment_mint
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawTreasuryTokens<'info>>,
+    amount: u64,
+) -> Result<()> {
+    ctx.accounts.withdraw(amount, ctx.remaining_accounts)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token::state::{Account as SplAccount, AccountState};
+
+    fn token_account(owner: Pubkey, mint: Pubkey) -> TokenAccount {
+        let mut data = vec![0; SplAccount::LEN];
+        SplAccount {
+            mint,
+            owner,
+            state: AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+
+        TokenAccount::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn token_shares_go_to_the_recipients_account_for_the_mint() {
+        let (recipient, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert!(pays_share(
+            &token_account(recipient, mint),
+            &recipient,
+            &mint
+        ));
+        assert!(!pays_share(
+            &token_account(Pubkey::new_unique(), mint),
+            &recipient,
+            &mint
+        ));
+        assert!(!pays_share(
+            &token_account(recipient, Pubkey::new_unique()),
+            &recipient,
+            &mint
+        ));
+    }
+}
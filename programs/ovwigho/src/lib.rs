@@ -2,8 +2,11 @@
 #![allow(deprecated)]
 
 pub mod error;
+pub mod events;
+#[allow(ambiguous_glob_reexports)]
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
@@ -33,33 +36,274 @@ pub mod ovwigho {
         name: String,
         uri: String,
         symbol: String,
+        delegate_to_config: bool,
+        allowlist: Option<AllowlistProof>,
     ) -> Result<()> {
-        instructions::mint_cnft::handler(ctx, name, uri, symbol)
+        instructions::mint_cnft::handler(ctx, name, uri, symbol, delegate_to_config, allowlist)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn burn_cnft<'info>(
         ctx: Context<'_, '_, 'info, 'info, BurncNFT<'info>>,
         root: [u8; 32],
         data_hash: [u8; 32],
-        creator_hash: [u8; 32],
         nonce: u64,
         index: u32,
         asset_data_hash: [u8; 32],
         flags: u8,
+        creator_hash: Option<[u8; 32]>,
     ) -> Result<()> {
         instructions::burn_cnft::handler(
             ctx,
             root,
             data_hash,
-            creator_hash,
             nonce,
             index,
             asset_data_hash,
             flags,
+            creator_hash,
         )
     }
 
     pub fn mint_nft(ctx: Context<MintNFT>, name: String, uri: String) -> Result<()> {
         instructions::mint_nft::handler(ctx, name, uri)
     }
+
+    pub fn close_craft_receipt(ctx: Context<CloseCraftReceipt>) -> Result<()> {
+        instructions::close_craft_receipt::handler(ctx)
+    }
+
+    pub fn burn_nft(ctx: Context<BurnNFT>) -> Result<()> {
+        instructions::burn_nft::handler(ctx)
+    }
+
+    pub fn decompose_nft(
+        ctx: Context<DecomposeNFT>,
+        name: String,
+        uri: String,
+        symbol: String,
+    ) -> Result<()> {
+        instructions::decompose_nft::handler(ctx, name, uri, symbol)
+    }
+
+    pub fn add_tier(
+        ctx: Context<AddTier>,
+        merge_cost: u8,
+        max_supply: Option<u32>,
+        name: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::add_tier::handler(ctx, merge_cost, max_supply, name, uri)
+    }
+
+    pub fn merge_nfts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MergeNFTs<'info>>,
+        name: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::merge_nfts::handler(ctx, name, uri)
+    }
+
+    pub fn level_up_nft(ctx: Context<LevelUpNFT>, payment: LevelUpPayment) -> Result<()> {
+        instructions::level_up_nft::handler(ctx, payment)
+    }
+
+    pub fn enable_moderation(
+        ctx: Context<EnableModeration>,
+        args: CreateNftCollectionArgs,
+    ) -> Result<()> {
+        instructions::enable_moderation::handler(ctx, args)
+    }
+
+    pub fn admin_burn_nft(ctx: Context<AdminBurnNFT>) -> Result<()> {
+        instructions::admin_burn_nft::handler(ctx)
+    }
+
+    pub fn admin_freeze_nft(ctx: Context<AdminFreezeNFT>, frozen: bool) -> Result<()> {
+        instructions::admin_freeze_nft::handler(ctx, frozen)
+    }
+
+    pub fn thaw_nft(ctx: Context<ThawNFT>) -> Result<()> {
+        instructions::thaw_nft::handler(ctx)
+    }
+
+    pub fn update_royalties(ctx: Context<UpdateRoyalties>, royalties: RoyaltyArgs) -> Result<()> {
+        instructions::update_royalties::handler(ctx, royalties)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_cnft_metadata<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateCnftMetadata<'info>>,
+        root: [u8; 32],
+        nonce: u64,
+        index: u32,
+        current_metadata: CnftMetadata,
+        update_args: CnftMetadataUpdate,
+        asset_data_hash: Option<[u8; 32]>,
+        flags: Option<u8>,
+    ) -> Result<()> {
+        instructions::update_cnft_metadata::handler(
+            ctx,
+            root,
+            nonce,
+            index,
+            current_metadata,
+            update_args,
+            asset_data_hash,
+            flags,
+        )
+    }
+
+    pub fn update_collection(
+        ctx: Context<UpdateCollection>,
+        args: UpdateCollectionArgs,
+    ) -> Result<()> {
+        instructions::update_collection::handler(ctx, args)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
+        instructions::update_config::handler(ctx, args)
+    }
+
+    pub fn set_token_payment(
+        ctx: Context<SetTokenPayment>,
+        cnft_price: u64,
+        nft_price: u64,
+    ) -> Result<()> {
+        instructions::set_token_payment::handler(ctx, cnft_price, nft_price)
+    }
+
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        expires_at: i64,
+        scope: u8,
+    ) -> Result<()> {
+        instructions::create_session::handler(ctx, session_key, expires_at, scope)
+    }
+
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        instructions::revoke_session::handler(ctx)
+    }
+
+    pub fn set_auto_craft(ctx: Context<SetAutoCraft>, enabled: bool) -> Result<()> {
+        instructions::set_auto_craft::handler(ctx, enabled)
+    }
+
+    pub fn set_contributors(
+        ctx: Context<SetContributors>,
+        open: bool,
+        contributors: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_contributors::handler(ctx, open, contributors)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn auto_burn_cnft<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AutoBurncNFT<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+        asset_data_hash: [u8; 32],
+        flags: u8,
+        creator_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::auto_burn_cnft::handler(
+            ctx,
+            root,
+            data_hash,
+            nonce,
+            index,
+            asset_data_hash,
+            flags,
+            creator_hash,
+        )
+    }
+
+    pub fn create_guild(
+        ctx: Context<CreateGuild>,
+        members: Vec<Pubkey>,
+        cnfts_per_craft: u32,
+    ) -> Result<()> {
+        instructions::create_guild::handler(ctx, members, cnfts_per_craft)
+    }
+
+    pub fn update_guild(
+        ctx: Context<UpdateGuild>,
+        members: Option<Vec<Pubkey>>,
+        cnfts_per_craft: Option<u32>,
+    ) -> Result<()> {
+        instructions::update_guild::handler(ctx, members, cnfts_per_craft)
+    }
+
+    pub fn guild_craft(ctx: Context<GuildCraft>, name: String, uri: String) -> Result<()> {
+        instructions::guild_craft::handler(ctx, name, uri)
+    }
+
+    pub fn start_season(
+        ctx: Context<StartSeason>,
+        start_ts: i64,
+        end_ts: i64,
+        cnfts_per_craft: u32,
+    ) -> Result<()> {
+        instructions::start_season::handler(ctx, start_ts, end_ts, cnfts_per_craft)
+    }
+
+    pub fn end_season(ctx: Context<EndSeason>) -> Result<()> {
+        instructions::end_season::handler(ctx)
+    }
+
+    pub fn create_drop(
+        ctx: Context<CreateDrop>,
+        mint_start: Option<i64>,
+        mint_end: Option<i64>,
+        max_supply: Option<u32>,
+        allowlist_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::create_drop::handler(ctx, mint_start, mint_end, max_supply, allowlist_root)
+    }
+
+    pub fn set_gates(
+        ctx: Context<SetGates>,
+        cnft_gate: Option<Gate>,
+        nft_gate: Option<Gate>,
+    ) -> Result<()> {
+        instructions::set_gates::handler(ctx, cnft_gate, nft_gate)
+    }
+
+    pub fn add_cnft_template(
+        ctx: Context<AddCnftTemplate>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::add_cnft_template::handler(ctx, name, symbol, uri)
+    }
+
+    pub fn airdrop_cnfts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AirdropcNFTs<'info>>,
+        recipients: Vec<Pubkey>,
+        template_ids: Vec<u32>,
+    ) -> Result<()> {
+        instructions::airdrop_cnfts::handler(ctx, recipients, template_ids)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config::handler(ctx)
+    }
+
+    pub fn withdraw_treasury<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawTreasury<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_treasury::handler(ctx, amount)
+    }
+
+    pub fn withdraw_treasury_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawTreasuryTokens<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_treasury_tokens::handler(ctx, amount)
+    }
 }
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_TEMPLATE_NAME_LEN: usize = 32;
+pub const MAX_TEMPLATE_SYMBOL_LEN: usize = 10;
+pub const MAX_TEMPLATE_URI_LEN: usize = 200;
+
+#[account]
+#[derive(InitSpace)]
+pub struct CnftTemplate {
+    pub config: Pubkey,
+    pub id: u32,
+    #[max_len(MAX_TEMPLATE_NAME_LEN)]
+    pub name: String,
+    #[max_len(MAX_TEMPLATE_SYMBOL_LEN)]
+    pub symbol: String,
+    #[max_len(MAX_TEMPLATE_URI_LEN)]
+    pub uri: String,
+    pub bump: u8,
+}
+
+impl CnftTemplate {
+    pub fn address(config: &Pubkey, id: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"template", config.as_ref(), &id.to_le_bytes()],
+            &crate::ID,
+        )
+        .0
+    }
+}
@@ -1,8 +1,93 @@
 use anchor_lang::prelude::*;
+use mpl_bubblegum::{
+    hash::hash_creators,
+    types::{Creator, MetadataArgsV2, TokenStandard},
+};
+use mpl_core::types::{
+    Attribute, Attributes, Edition, PermanentFreezeDelegate, Plugin, PluginAuthority,
+    PluginAuthorityPair,
+};
+
+use crate::{PlayerProgress, Season};
+
+pub const MAX_CNFT_CREATORS: usize = 5;
+pub const MAX_URI_PREFIX_LEN: usize = 128;
+pub const MAX_REVENUE_SHARES: usize = 5;
+pub const MAX_SPONSORS: usize = 5;
+pub const MAX_CRANKS: usize = 5;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
+    pub authority: Pubkey,
+    pub cnft_collection: Pubkey,
+    pub nft_collection: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub total_cnfts_minted: u32,
+    pub total_nfts_minted: u32,
+    pub total_editions: u32,
+    pub nft_max_supply: Option<u32>,
+    pub cnft_seller_fee_basis_points: u16,
+    pub cnft_is_mutable: bool,
+    #[max_len(MAX_CNFT_CREATORS)]
+    pub cnft_creators: Vec<CnftCreator>,
+    pub soulbound: bool,
+    pub moderation: bool,
+    pub total_nfts_frozen: u32,
+    pub total_nfts_burned: u32,
+    pub burn_reward_points: u32,
+    pub cnfts_per_craft: u32,
+    pub decompose_loss_bps: u16,
+    pub tier_count: u8,
+    pub level_up_points: u32,
+    pub level_up_burns: u32,
+    #[max_len(MAX_URI_PREFIX_LEN)]
+    pub level_uri_prefix: String,
+    pub cnft_mint_fee: u64,
+    pub nft_mint_fee: u64,
+    #[max_len(MAX_REVENUE_SHARES)]
+    pub revenue_shares: Vec<RevenueShare>,
+    pub treasury_bump: u8,
+    pub payment_mint: Option<Pubkey>,
+    pub cnft_token_price: u64,
+    pub nft_token_price: u64,
+    #[max_len(MAX_SPONSORS)]
+    pub sponsors: Vec<Pubkey>,
+    #[max_len(MAX_CRANKS)]
+    pub cranks: Vec<Pubkey>,
+    pub season_count: u32,
+    pub active_season: Option<u32>,
+    pub drop_count: u32,
+    pub drops_only: bool,
+    pub cnft_gate: Option<Gate>,
+    pub nft_gate: Option<Gate>,
+    pub template_count: u32,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct CnftCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RevenueShare {
+    pub recipient: Pubkey,
+    pub basis_points: u16,
+}
+
+/// Holding requirement a player must meet to mint or craft.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub enum Gate {
+    CoreCollection { collection: Pubkey },
+    TokenBalance { mint: Pubkey, min_amount: u64 },
+}
+
+/// Config layout written by deployments that predate the crafting features.
+/// Only read by `migrate_config`.
+#[derive(AnchorDeserialize)]
+pub struct LegacyConfig {
     pub authority: Pubkey,
     pub cnft_collection: Pubkey,
     pub nft_collection: Pubkey,
@@ -11,3 +96,459 @@ pub struct Config {
     pub total_nfts_minted: u32,
     pub bump: u8,
 }
+
+impl LegacyConfig {
+    pub const LEN: usize = 32 * 4 + 4 + 4 + 1;
+
+    /// Carries the legacy fields over and fills everything else with the same
+    /// defaults `initialize` uses. Editions continue from the NFTs already minted.
+    pub fn migrate(self, config: Pubkey, treasury_bump: u8) -> Config {
+        Config {
+            total_cnfts_minted: self.total_cnfts_minted,
+            total_nfts_minted: self.total_nfts_minted,
+            total_editions: self.total_nfts_minted,
+            ..Config::new(
+                self.authority,
+                self.cnft_collection,
+                self.nft_collection,
+                self.merkle_tree,
+                config,
+                treasury_bump,
+                self.bump,
+            )
+        }
+    }
+}
+
+/// What an action costs the player.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Payment {
+    Lamports(u64),
+    Tokens(u64),
+}
+
+impl Config {
+    pub fn new(
+        authority: Pubkey,
+        cnft_collection: Pubkey,
+        nft_collection: Pubkey,
+        merkle_tree: Pubkey,
+        config: Pubkey,
+        treasury_bump: u8,
+        bump: u8,
+    ) -> Self {
+        Self {
+            authority,
+            cnft_collection,
+            nft_collection,
+            merkle_tree,
+            total_cnfts_minted: 0,
+            total_nfts_minted: 0,
+            total_editions: 0,
+            nft_max_supply: None,
+            cnft_seller_fee_basis_points: 0,
+            cnft_is_mutable: false,
+            cnft_creators: vec![CnftCreator {
+                address: config,
+                share: 100,
+            }],
+            soulbound: false,
+            moderation: false,
+            total_nfts_frozen: 0,
+            total_nfts_burned: 0,
+            burn_reward_points: 0,
+            cnfts_per_craft: 5,
+            decompose_loss_bps: 2_000,
+            tier_count: 0,
+            level_up_points: 0,
+            level_up_burns: 0,
+            level_uri_prefix: String::new(),
+            cnft_mint_fee: 0,
+            nft_mint_fee: 0,
+            revenue_shares: vec![],
+            treasury_bump,
+            payment_mint: None,
+            cnft_token_price: 0,
+            nft_token_price: 0,
+            sponsors: vec![],
+            cranks: vec![],
+            season_count: 0,
+            active_season: None,
+            drop_count: 0,
+            drops_only: false,
+            cnft_gate: None,
+            nft_gate: None,
+            template_count: 0,
+            bump,
+        }
+    }
+
+    /// Actions are priced in the payment token once one is set, and in
+    /// lamports otherwise.
+    /// Switches payments to `payment_mint` at the given prices, or back to SOL
+    /// fees when there is no mint.
+    pub fn set_token_payment(
+        &mut self,
+        payment_mint: Option<Pubkey>,
+        cnft_price: u64,
+        nft_price: u64,
+    ) {
+        let prices = if payment_mint.is_some() {
+            (cnft_price, nft_price)
+        } else {
+            (0, 0)
+        };
+
+        self.payment_mint = payment_mint;
+        (self.cnft_token_price, self.nft_token_price) = prices;
+    }
+
+    pub fn payment(&self, lamports: u64, tokens: u64) -> Payment {
+        match self.payment_mint {
+            Some(_) => Payment::Tokens(tokens),
+            None => Payment::Lamports(lamports),
+        }
+    }
+
+    /// The transaction signer can always pay for itself; anyone else needs to
+    /// be on the sponsor allowlist, unless that list is empty.
+    pub fn can_pay_for(&self, payer: &Pubkey, signer: &Pubkey) -> bool {
+        payer == signer || self.sponsors.is_empty() || self.sponsors.contains(payer)
+    }
+
+    /// Creators written into every minted leaf. The config PDA signs the mint,
+    /// so its own entry is the only one marked as verified.
+    pub fn leaf_creators(&self, config: Pubkey) -> Vec<Creator> {
+        self.cnft_creators
+            .iter()
+            .map(|creator| Creator {
+                address: creator.address,
+                verified: creator.address == config,
+                share: creator.share,
+            })
+            .collect()
+    }
+
+    /// Drops stamp themselves into the leaf as an extra creator, so once the
+    /// config has a drop one creator slot is kept free for the stamp.
+    pub fn max_cnft_creators(&self) -> usize {
+        if self.drop_count > 0 {
+            MAX_CNFT_CREATORS - 1
+        } else {
+            MAX_CNFT_CREATORS
+        }
+    }
+
+    pub fn leaf_creator_hash(&self, config: Pubkey) -> [u8; 32] {
+        hash_creators(&self.leaf_creators(config))
+    }
+
+    pub fn leaf_metadata(
+        &self,
+        config: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> MetadataArgsV2 {
+        MetadataArgsV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: self.cnft_seller_fee_basis_points,
+            primary_sale_happened: false,
+            is_mutable: self.cnft_is_mutable,
+            token_standard: Some(TokenStandard::NonFungible),
+            creators: self.leaf_creators(config),
+            collection: Some(self.cnft_collection),
+        }
+    }
+
+    /// The active season's recipe takes precedence over the config default.
+    /// `season` must already be checked to be the active one.
+    pub fn craft_cost(&self, season: Option<&Season>) -> u32 {
+        match (self.active_season, season) {
+            (Some(_), Some(season)) => season.cnfts_per_craft,
+            _ => self.cnfts_per_craft,
+        }
+    }
+
+    pub fn has_supply_left(&self) -> bool {
+        self.nft_max_supply
+            .is_none_or(|max_supply| self.total_editions < max_supply)
+    }
+
+    /// Counts a newly crafted NFT in `nft_collection`.
+    pub fn record_craft(&mut self, owner_progress: &mut PlayerProgress) {
+        self.total_nfts_minted += 1;
+        self.total_editions += 1;
+        self.record_soulbound(owner_progress);
+    }
+
+    /// Soulbound assets are created frozen, so they are counted as frozen for
+    /// the config and the owner just like a moderation freeze.
+    pub fn record_soulbound(&mut self, owner_progress: &mut PlayerProgress) {
+        if self.soulbound {
+            self.total_nfts_frozen += 1;
+            owner_progress.total_nfts_frozen += 1;
+        }
+    }
+
+    /// Plugins every crafted NFT is created with: the program-owned attributes,
+    /// its edition number within its collection, and a permanent freeze
+    /// delegate when the collection is soulbound or moderated.
+    pub fn asset_plugins(
+        &self,
+        config: Pubkey,
+        edition: u32,
+        attributes: Vec<Attribute>,
+    ) -> Vec<PluginAuthorityPair> {
+        let mut plugins = vec![
+            PluginAuthorityPair {
+                plugin: Plugin::Attributes(Attributes {
+                    attribute_list: attributes,
+                }),
+                authority: Some(PluginAuthority::Address { address: config }),
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::Edition(Edition { number: edition }),
+                authority: None,
+            },
+        ];
+
+        if self.soulbound || self.moderation {
+            plugins.push(PluginAuthorityPair {
+                plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
+                    frozen: self.soulbound,
+                }),
+                authority: Some(PluginAuthority::Address { address: config }),
+            });
+        }
+
+        plugins
+    }
+
+    /// Each revenue share's cut of a withdrawal, in `revenue_shares` order.
+    /// Rounding dust is left for the authority.
+    pub fn revenue_cuts(&self, amount: u64) -> Vec<u64> {
+        self.revenue_shares
+            .iter()
+            .map(|share| (amount as u128 * share.basis_points as u128 / 10_000) as u64)
+            .collect()
+    }
+
+    /// Number of cNFTs handed back when a crafted NFT is decomposed, after the
+    /// configured loss is taken off the `cnfts_burned` its receipt records.
+    pub fn decompose_yield(&self, cnfts_burned: u32) -> u32 {
+        (cnfts_burned as u64 * (10_000 - self.decompose_loss_bps as u64) / 10_000) as u32
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub fn test_config(config: Pubkey) -> Config {
+        Config::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            config,
+            255,
+            255,
+        )
+    }
+
+    #[test]
+    fn migrate_keeps_legacy_fields_and_continues_editions() {
+        let authority = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+
+        let mut data = vec![];
+        for key in [
+            authority,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ] {
+            data.extend_from_slice(key.as_ref());
+        }
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.push(254);
+        assert_eq!(data.len(), LegacyConfig::LEN);
+
+        let migrated = LegacyConfig::deserialize(&mut &data[..])
+            .unwrap()
+            .migrate(config, 253);
+
+        assert_eq!(migrated.authority, authority);
+        assert_eq!(migrated.total_cnfts_minted, 7);
+        assert_eq!(migrated.total_nfts_minted, 3);
+        assert_eq!(migrated.total_editions, 3);
+        assert_eq!(migrated.treasury_bump, 253);
+        assert_eq!(migrated.bump, 254);
+        assert_eq!(migrated.cnfts_per_craft, 5);
+        assert_eq!(migrated.leaf_creators(config)[0].address, config);
+        assert!(migrated.leaf_creators(config)[0].verified);
+    }
+
+    #[test]
+    fn leaf_creators_verify_only_the_config_and_hash_like_the_minted_leaf() {
+        let config = Pubkey::new_unique();
+        let studio = Pubkey::new_unique();
+        let mut state = test_config(config);
+        state.cnft_creators = vec![
+            CnftCreator {
+                address: config,
+                share: 0,
+            },
+            CnftCreator {
+                address: studio,
+                share: 100,
+            },
+        ];
+
+        let creators = state.leaf_creators(config);
+        assert!(creators[0].verified);
+        assert!(!creators[1].verified);
+        assert_eq!(creators[1].share, 100);
+
+        let leaf = state.leaf_metadata(config, "a".into(), "A".into(), "uri".into());
+        assert_eq!(
+            state.leaf_creator_hash(config),
+            hash_creators(&leaf.creators)
+        );
+    }
+
+    fn freeze_delegate(plugins: &[PluginAuthorityPair]) -> Option<bool> {
+        plugins.iter().find_map(|pair| match &pair.plugin {
+            Plugin::PermanentFreezeDelegate(delegate) => Some(delegate.frozen),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn soulbound_assets_are_created_frozen_by_the_config() {
+        let config = Pubkey::new_unique();
+        let mut state = test_config(config);
+        assert_eq!(
+            freeze_delegate(&state.asset_plugins(config, 1, vec![])),
+            None
+        );
+
+        state.moderation = true;
+        assert_eq!(
+            freeze_delegate(&state.asset_plugins(config, 1, vec![])),
+            Some(false)
+        );
+
+        state.soulbound = true;
+        let plugins = state.asset_plugins(config, 1, vec![]);
+        assert_eq!(freeze_delegate(&plugins), Some(true));
+        assert_eq!(
+            plugins.last().unwrap().authority,
+            Some(PluginAuthority::Address { address: config })
+        );
+    }
+
+    #[test]
+    fn decompose_yield_takes_the_loss_off_what_the_craft_burned() {
+        let mut state = test_config(Pubkey::new_unique());
+        assert_eq!(state.decompose_yield(5), 4);
+
+        // Crafted for 2 during a cheap season, decomposed after the config
+        // price went up: the refund follows the receipt, never the price.
+        state.cnfts_per_craft = 50;
+        state.decompose_loss_bps = 0;
+        assert_eq!(state.decompose_yield(2), 2);
+        assert_eq!(state.decompose_yield(0), 0);
+
+        state.decompose_loss_bps = 10_000;
+        assert_eq!(state.decompose_yield(u32::MAX), 0);
+
+        state.decompose_loss_bps = 0;
+        assert_eq!(state.decompose_yield(u32::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn crafts_take_the_next_edition_until_max_supply() {
+        let config = Pubkey::new_unique();
+        let mut state = test_config(config);
+        state.nft_max_supply = Some(2);
+
+        for number in 1..=2 {
+            assert!(state.has_supply_left());
+            let plugins = state.asset_plugins(config, state.total_editions + 1, vec![]);
+            assert!(matches!(
+                plugins[1].plugin,
+                Plugin::Edition(Edition { number: n }) if n == number
+            ));
+            state.record_craft(&mut PlayerProgress::default());
+        }
+
+        assert!(!state.has_supply_left());
+        assert_eq!(state.total_nfts_minted, 2);
+    }
+
+    #[test]
+    fn revenue_cuts_round_down_and_leave_dust_for_the_authority() {
+        let mut state = test_config(Pubkey::new_unique());
+        assert!(state.revenue_cuts(1_000).is_empty());
+
+        state.revenue_shares = [3_333, 3_333, 3_334]
+            .into_iter()
+            .map(|basis_points| RevenueShare {
+                recipient: Pubkey::new_unique(),
+                basis_points,
+            })
+            .collect();
+
+        let cuts = state.revenue_cuts(1_001);
+        assert_eq!(cuts, vec![333, 333, 333]);
+        assert_eq!(1_001 - cuts.iter().sum::<u64>(), 2);
+
+        assert_eq!(
+            state.revenue_cuts(u64::MAX).iter().sum::<u64>(),
+            u64::MAX - 1
+        );
+    }
+
+    #[test]
+    fn actions_are_priced_in_tokens_once_a_payment_mint_is_set() {
+        let mut state = test_config(Pubkey::new_unique());
+        assert_eq!(state.payment(10, 20), Payment::Lamports(10));
+
+        state.set_token_payment(Some(Pubkey::new_unique()), 20, 0);
+        assert_eq!(
+            state.payment(10, state.cnft_token_price),
+            Payment::Tokens(20)
+        );
+        assert_eq!(state.payment(10, state.nft_token_price), Payment::Tokens(0));
+
+        state.set_token_payment(None, 20, 30);
+        assert_eq!(state.payment_mint, None);
+        assert_eq!(
+            state.payment(10, state.cnft_token_price),
+            Payment::Lamports(10)
+        );
+        assert_eq!((state.cnft_token_price, state.nft_token_price), (0, 0));
+    }
+
+    #[test]
+    fn only_allowlisted_sponsors_can_pay_for_someone_else() {
+        let mut state = test_config(Pubkey::new_unique());
+        let (player, sponsor, stranger) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        assert!(state.can_pay_for(&stranger, &player));
+
+        state.sponsors = vec![sponsor];
+        assert!(state.can_pay_for(&player, &player));
+        assert!(state.can_pay_for(&sponsor, &player));
+        assert!(!state.can_pay_for(&stranger, &player));
+    }
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Contribution {
+    pub beneficiary: Pubkey,
+    pub contributor: Pubkey,
+    pub cnfts_burned: u32,
+    pub bump: u8,
+}
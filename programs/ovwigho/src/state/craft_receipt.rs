@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use solana_keccak_hasher as keccak;
+
+/// How a crafted asset was paid for.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Recipe {
+    /// Burned cNFTs, priced by the config or the season with this id (0 when
+    /// no season was running).
+    Burn { season_id: u32 },
+    /// A guild's pooled cNFT burns.
+    Guild { guild: Pubkey },
+    /// NFTs of the preceding tier merged into `tier`.
+    Merge { tier: u8, nfts_burned: u8 },
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CraftReceipt {
+    pub player: Pubkey,
+    pub asset: Pubkey,
+    pub recipe: Recipe,
+    pub cnfts_burned: u32,
+    pub slot: u64,
+    pub burned_leaves_hash: [u8; 32],
+    /// Paid the receipt's rent and gets it back when the receipt is closed.
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+impl CraftReceipt {
+    /// Extends a running hash of burned asset ids. The chain is never reset, so
+    /// burns left over after a craft are covered by the next receipt.
+    pub fn chain_burn(hash: &[u8; 32], asset_id: &Pubkey) -> [u8; 32] {
+        keccak::hashv(&[hash, asset_id.as_ref()]).to_bytes()
+    }
+}
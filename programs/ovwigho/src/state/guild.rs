@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, CraftReceipt};
+
+pub const MAX_GUILD_MEMBERS: usize = 16;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Guild {
+    pub config: Pubkey,
+    pub leader: Pubkey,
+    #[max_len(MAX_GUILD_MEMBERS)]
+    pub members: Vec<Pubkey>,
+    pub total_cnfts_burned: u32,
+    pub burned_leaves_hash: [u8; 32],
+    pub cnfts_per_craft: u32,
+    pub total_nfts_minted: u32,
+    pub bump: u8,
+}
+
+impl Guild {
+    /// Players can only be added to a guild with their own signature; dropping
+    /// members needs no consent.
+    pub fn check_consent(current: &[Pubkey], members: &[Pubkey], signers: &[Pubkey]) -> Result<()> {
+        for member in members {
+            require!(
+                current.contains(member) || signers.contains(member),
+                ErrorCode::MemberConsentMissing
+            );
+        }
+
+        Ok(())
+    }
+
+    /// The guild's own recipe can raise the cost of a craft, never lower it
+    /// below the config's or the active season's.
+    pub fn craft_cost(&self, recipe_cost: u32) -> u32 {
+        self.cnfts_per_craft.max(recipe_cost)
+    }
+
+    pub fn record_cnft_burn(&mut self, asset_id: Pubkey) {
+        self.total_cnfts_burned += 1;
+        self.burned_leaves_hash = CraftReceipt::chain_burn(&self.burned_leaves_hash, &asset_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joining_needs_a_signature_and_cost_never_drops_below_the_recipe() {
+        let (kept, invited, stranger) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        assert!(Guild::check_consent(&[kept], &[kept, invited], &[invited]).is_ok());
+        assert!(Guild::check_consent(&[kept], &[kept, stranger], &[invited]).is_err());
+        assert!(Guild::check_consent(&[kept, invited], &[], &[]).is_ok());
+
+        let guild = Guild {
+            config: Pubkey::new_unique(),
+            leader: Pubkey::new_unique(),
+            members: vec![kept],
+            total_cnfts_burned: 0,
+            burned_leaves_hash: [0; 32],
+            cnfts_per_craft: 1,
+            total_nfts_minted: 0,
+            bump: 255,
+        };
+        assert_eq!(guild.craft_cost(5), 5);
+        assert_eq!(guild.craft_cost(0), 1);
+    }
+}
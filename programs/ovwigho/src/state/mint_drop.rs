@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::types::{Creator, MetadataArgsV2};
+use solana_keccak_hasher as keccak;
+
+use crate::{error::ErrorCode, MAX_CNFT_CREATORS};
+
+#[account]
+#[derive(InitSpace)]
+pub struct MintDrop {
+    pub config: Pubkey,
+    pub id: u32,
+    pub mint_start: Option<i64>,
+    pub mint_end: Option<i64>,
+    pub max_supply: Option<u32>,
+    pub total_minted: u32,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistClaim {
+    pub mint_drop: Pubkey,
+    pub wallet: Pubkey,
+    pub claimed: u32,
+    pub bump: u8,
+}
+
+impl MintDrop {
+    pub fn is_open(&self, now: i64) -> bool {
+        self.mint_start.is_none_or(|start| start <= now)
+            && self.mint_end.is_none_or(|end| now < end)
+    }
+
+    pub fn is_sold_out(&self) -> bool {
+        self.max_supply
+            .is_some_and(|max_supply| self.total_minted >= max_supply)
+    }
+
+    /// Verifies `proof` for the `keccak(wallet || allowance_le)` leaf against the
+    /// allowlist root. Pairs are hashed in sorted order.
+    pub fn is_allowlisted(&self, wallet: &Pubkey, allowance: u32, proof: &[[u8; 32]]) -> bool {
+        let Some(root) = self.allowlist_root else {
+            return false;
+        };
+
+        let leaf = keccak::hashv(&[wallet.as_ref(), &allowance.to_le_bytes()]).to_bytes();
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                keccak::hashv(&[&node, sibling]).to_bytes()
+            } else {
+                keccak::hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+
+        computed == root
+    }
+
+    /// Stamps the drop into the leaf as an unverified creator with no share,
+    /// so it shows up in the leaf's creator hash.
+    pub fn stamp(&self, drop: Pubkey, metadata: &mut MetadataArgsV2) -> Result<()> {
+        require!(
+            metadata.creators.len() < MAX_CNFT_CREATORS,
+            ErrorCode::TooManyCreators
+        );
+
+        metadata.creators.push(Creator {
+            address: drop,
+            verified: false,
+            share: 0,
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(wallet: &Pubkey, allowance: u32) -> [u8; 32] {
+        keccak::hashv(&[wallet.as_ref(), &allowance.to_le_bytes()]).to_bytes()
+    }
+
+    fn pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            keccak::hashv(&[&a, &b]).to_bytes()
+        } else {
+            keccak::hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn allowlist_proofs_check_wallet_and_allowance() {
+        let wallets: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = wallets.iter().map(|wallet| leaf(wallet, 2)).collect();
+        let left = pair(leaves[0], leaves[1]);
+        let root = pair(left, leaves[2]);
+
+        let mut mint_drop = MintDrop {
+            config: Pubkey::new_unique(),
+            id: 0,
+            mint_start: None,
+            mint_end: None,
+            max_supply: None,
+            total_minted: 0,
+            allowlist_root: None,
+            bump: 255,
+        };
+        assert!(!mint_drop.is_allowlisted(&wallets[2], 2, &[left]));
+
+        mint_drop.allowlist_root = Some(root);
+        assert!(mint_drop.is_allowlisted(&wallets[0], 2, &[leaves[1], leaves[2]]));
+        assert!(mint_drop.is_allowlisted(&wallets[1], 2, &[leaves[0], leaves[2]]));
+        assert!(mint_drop.is_allowlisted(&wallets[2], 2, &[left]));
+
+        assert!(!mint_drop.is_allowlisted(&wallets[2], 3, &[left]));
+        assert!(!mint_drop.is_allowlisted(&Pubkey::new_unique(), 2, &[left]));
+        assert!(!mint_drop.is_allowlisted(&wallets[0], 2, &[leaves[2], leaves[1]]));
+    }
+
+    #[test]
+    fn drops_need_a_free_creator_slot_for_their_stamp() {
+        let config = Pubkey::new_unique();
+        let mut state = crate::state::config::tests::test_config(config);
+        state.cnft_creators = (0..MAX_CNFT_CREATORS)
+            .map(|i| crate::CnftCreator {
+                address: if i == 0 { config } else { Pubkey::new_unique() },
+                share: if i == 0 { 100 } else { 0 },
+            })
+            .collect();
+        let mint_drop = MintDrop {
+            config,
+            id: 1,
+            mint_start: None,
+            mint_end: None,
+            max_supply: None,
+            total_minted: 0,
+            allowlist_root: None,
+            bump: 255,
+        };
+        let drop = Pubkey::new_unique();
+
+        let mut leaf = state.leaf_metadata(config, "a".into(), "A".into(), "uri".into());
+        assert!(mint_drop.stamp(drop, &mut leaf).is_err());
+
+        state.drop_count = 1;
+        assert_eq!(state.max_cnft_creators(), MAX_CNFT_CREATORS - 1);
+        assert!(state.cnft_creators.len() > state.max_cnft_creators());
+
+        state.cnft_creators.pop();
+        let mut leaf = state.leaf_metadata(config, "a".into(), "A".into(), "uri".into());
+        mint_drop.stamp(drop, &mut leaf).unwrap();
+        assert_eq!(leaf.creators.len(), MAX_CNFT_CREATORS);
+        assert_eq!(leaf.creators.last().unwrap().address, drop);
+    }
+}
@@ -1,5 +1,21 @@
+pub mod cnft_template;
 pub mod config;
+pub mod contribution;
+pub mod craft_receipt;
+pub mod guild;
+pub mod mint_drop;
 pub mod player_progress;
+pub mod season;
+pub mod session_token;
+pub mod tier;
 
+pub use cnft_template::*;
 pub use config::*;
+pub use contribution::*;
+pub use craft_receipt::*;
+pub use guild::*;
+pub use mint_drop::*;
 pub use player_progress::*;
+pub use season::*;
+pub use session_token::*;
+pub use tier::*;
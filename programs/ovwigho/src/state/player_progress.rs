@@ -1,11 +1,243 @@
+use crate::{error::ErrorCode, Config, CraftReceipt, Season, MAX_TIERS};
 use anchor_lang::prelude::*;
 
+pub const MAX_CONTRIBUTORS: usize = 8;
+
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct PlayerProgress {
     pub player: Pubkey,
     pub authority: Pubkey,
     pub total_cnfts_burned: u32,
     pub total_nfts_minted: u32,
+    pub total_nfts_frozen: u32,
+    pub total_nfts_burned: u32,
+    pub crafting_points: u32,
+    pub craft_count: u32,
+    pub burned_leaves_hash: [u8; 32],
+    pub tier_nfts_minted: [u32; MAX_TIERS],
+    pub auto_craft: bool,
+    pub open_contributions: bool,
+    #[max_len(MAX_CONTRIBUTORS)]
+    pub contributors: Vec<Pubkey>,
+    pub season_id: u32,
+    pub season_cnfts_burned: u32,
+    pub season_nfts_minted: u32,
     pub bump: u8,
 }
+
+impl PlayerProgress {
+    /// Approved cranks may only act for players who opted in to auto crafting.
+    pub fn allows_crank(&self, config: &Config, cranker: &Pubkey) -> bool {
+        self.auto_craft && config.cranks.contains(cranker)
+    }
+
+    pub fn accepts_contribution_from(&self, contributor: &Pubkey) -> bool {
+        self.open_contributions || self.contributors.contains(contributor)
+    }
+
+    /// Resets the per-season counters the first time progress is touched in a
+    /// new season, or once seasons are switched off. Unspent burns and the burn
+    /// hash carry over.
+    pub fn sync_season(&mut self, season: Option<&Season>) {
+        let season_id = season.map_or(0, |season| season.id);
+        if season_id == self.season_id {
+            return;
+        }
+
+        self.season_id = season_id;
+        self.season_cnfts_burned = 0;
+        self.season_nfts_minted = 0;
+    }
+
+    pub fn record_cnft_burn(&mut self, asset_id: Pubkey) {
+        self.total_cnfts_burned += 1;
+        self.season_cnfts_burned += 1;
+        self.burned_leaves_hash = CraftReceipt::chain_burn(&self.burned_leaves_hash, &asset_id);
+    }
+
+    /// Spends `cost` burns on a craft and returns the burn hash for its
+    /// receipt. The hash keeps chaining, so leftover burns carry forward.
+    pub fn spend_burns(&mut self, cost: u32) -> Result<[u8; 32]> {
+        self.total_cnfts_burned = self
+            .total_cnfts_burned
+            .checked_sub(cost)
+            .ok_or(ErrorCode::NotEnoughBurns)?;
+
+        Ok(self.burned_leaves_hash)
+    }
+
+    /// Address the next crafted asset is minted at when no asset keypair signs.
+    pub fn next_asset_address(&self, config: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"asset",
+                config.as_ref(),
+                self.player.as_ref(),
+                &self.craft_count.to_le_bytes(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    /// Rewards burning a crafted NFT. The minted total is history and is left
+    /// alone, whoever crafted the burned asset.
+    pub fn record_nft_mint(&mut self) {
+        self.total_nfts_minted += 1;
+        self.season_nfts_minted += 1;
+    }
+
+    pub fn record_nft_burn(&mut self, reward_points: u32) {
+        self.total_nfts_burned += 1;
+        self.crafting_points += reward_points;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burning_an_nft_rewards_points_without_touching_minted() {
+        let mut progress = PlayerProgress {
+            total_nfts_minted: 0,
+            ..Default::default()
+        };
+
+        progress.record_nft_burn(25);
+        progress.record_nft_burn(25);
+
+        assert_eq!(progress.total_nfts_burned, 2);
+        assert_eq!(progress.crafting_points, 50);
+        assert_eq!(progress.total_nfts_minted, 0);
+    }
+
+    #[test]
+    fn each_craft_gets_its_own_derived_asset_address() {
+        let config = Pubkey::new_unique();
+        let mut progress = PlayerProgress {
+            player: Pubkey::new_unique(),
+            ..Default::default()
+        };
+
+        let (first, _) = progress.next_asset_address(&config);
+        assert_eq!(first, progress.next_asset_address(&config).0);
+
+        progress.craft_count += 1;
+        let (second, bump) = progress.next_asset_address(&config);
+        assert_ne!(first, second);
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[
+                    b"asset",
+                    config.as_ref(),
+                    progress.player.as_ref(),
+                    &1u32.to_le_bytes(),
+                    &[bump],
+                ],
+                &crate::ID,
+            )
+            .unwrap(),
+            second
+        );
+    }
+
+    #[test]
+    fn leftover_burns_stay_in_the_next_receipts_hash() {
+        let mut progress = PlayerProgress::default();
+        let burns: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+
+        for asset_id in &burns[..3] {
+            progress.record_cnft_burn(*asset_id);
+        }
+        let first = progress.spend_burns(2).unwrap();
+        assert_eq!(progress.total_cnfts_burned, 1);
+
+        progress.record_cnft_burn(burns[3]);
+        let second = progress.spend_burns(2).unwrap();
+        assert_eq!(second, CraftReceipt::chain_burn(&first, &burns[3]));
+
+        assert!(progress.spend_burns(1).is_err());
+    }
+
+    #[test]
+    fn cranks_need_approval_and_the_players_opt_in() {
+        let crank = Pubkey::new_unique();
+        let mut config = crate::state::config::tests::test_config(Pubkey::new_unique());
+        let mut progress = PlayerProgress::default();
+
+        config.cranks = vec![crank];
+        assert!(!progress.allows_crank(&config, &crank));
+
+        progress.auto_craft = true;
+        assert!(progress.allows_crank(&config, &crank));
+        assert!(!progress.allows_crank(&config, &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn contributions_need_an_open_pool_or_a_listed_contributor() {
+        let teammate = Pubkey::new_unique();
+        let mut progress = PlayerProgress::default();
+        assert!(!progress.accepts_contribution_from(&teammate));
+
+        progress.contributors = vec![teammate];
+        assert!(progress.accepts_contribution_from(&teammate));
+        assert!(!progress.accepts_contribution_from(&Pubkey::new_unique()));
+
+        progress.open_contributions = true;
+        assert!(progress.accepts_contribution_from(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn a_new_season_only_resets_season_counters() {
+        let mut progress = PlayerProgress::default();
+        progress.record_cnft_burn(Pubkey::new_unique());
+        progress.season_nfts_minted = 1;
+        let hash = progress.burned_leaves_hash;
+
+        let season = Season {
+            config: Pubkey::new_unique(),
+            id: 1,
+            start_ts: 0,
+            end_ts: 10,
+            cnfts_per_craft: 3,
+            bump: 255,
+        };
+        progress.sync_season(Some(&season));
+
+        assert_eq!(progress.season_id, 1);
+        assert_eq!(progress.season_cnfts_burned, 0);
+        assert_eq!(progress.season_nfts_minted, 0);
+        assert_eq!(progress.total_cnfts_burned, 1);
+        assert_eq!(progress.burned_leaves_hash, hash);
+
+        progress.record_cnft_burn(Pubkey::new_unique());
+        progress.sync_season(Some(&season));
+        assert_eq!(progress.season_cnfts_burned, 1);
+    }
+
+    #[test]
+    fn mints_after_a_season_change_count_toward_the_new_season() {
+        let mut progress = PlayerProgress {
+            season_id: 1,
+            season_nfts_minted: 4,
+            total_nfts_minted: 4,
+            ..Default::default()
+        };
+
+        let season = Season {
+            config: Pubkey::new_unique(),
+            id: 2,
+            start_ts: 0,
+            end_ts: 10,
+            cnfts_per_craft: 3,
+            bump: 255,
+        };
+        progress.sync_season(Some(&season));
+        progress.record_nft_mint();
+
+        assert_eq!(progress.season_id, 2);
+        assert_eq!(progress.season_nfts_minted, 1);
+        assert_eq!(progress.total_nfts_minted, 5);
+    }
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    pub config: Pubkey,
+    pub id: u32,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub cnfts_per_craft: u32,
+    pub bump: u8,
+}
+
+impl Season {
+    pub fn is_open(&self, now: i64) -> bool {
+        self.start_ts <= now && now < self.end_ts
+    }
+}
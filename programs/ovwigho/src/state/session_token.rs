@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+pub const SESSION_SCOPE_BURN_CNFT: u8 = 1 << 0;
+pub const SESSION_SCOPE_MINT_CNFT: u8 = 1 << 1;
+pub const SESSION_SCOPE_MINT_NFT: u8 = 1 << 2;
+pub const SESSION_SCOPE_ALL: u8 =
+    SESSION_SCOPE_BURN_CNFT | SESSION_SCOPE_MINT_CNFT | SESSION_SCOPE_MINT_NFT;
+
+#[account]
+#[derive(InitSpace)]
+pub struct SessionToken {
+    pub player: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub scope: u8,
+    pub bump: u8,
+}
+
+impl SessionToken {
+    pub fn allows(&self, scope: u8, now: i64) -> bool {
+        now < self.expires_at && self.scope & scope == scope
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_TIERS: usize = 4;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Tier {
+    pub config: Pubkey,
+    pub index: u8,
+    pub collection: Pubkey,
+    pub merge_cost: u8,
+    pub max_supply: Option<u32>,
+    /// Also the edition number of the latest asset merged into the tier.
+    pub total_minted: u32,
+    pub total_burned: u32,
+    pub bump: u8,
+}
+
+impl Tier {
+    pub fn has_supply_left(&self) -> bool {
+        self.max_supply
+            .is_none_or(|max_supply| self.total_minted < max_supply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{state::config::tests::test_config, PlayerProgress};
+    use mpl_core::types::{Edition, Plugin};
+
+    #[test]
+    fn merges_number_and_cap_editions_per_tier() {
+        let config_key = Pubkey::new_unique();
+        let mut config = test_config(config_key);
+        config.nft_max_supply = Some(3);
+        config.total_editions = 3;
+        config.soulbound = true;
+
+        let mut tier = Tier {
+            config: config_key,
+            index: 1,
+            collection: Pubkey::new_unique(),
+            merge_cost: 2,
+            max_supply: Some(2),
+            total_minted: 0,
+            total_burned: 0,
+            bump: 255,
+        };
+        let mut player_progress = PlayerProgress::default();
+        assert!(!config.has_supply_left());
+
+        for number in 1..=2 {
+            assert!(tier.has_supply_left());
+            let plugins = config.asset_plugins(config_key, tier.total_minted + 1, vec![]);
+            assert!(matches!(
+                plugins[1].plugin,
+                Plugin::Edition(Edition { number: n }) if n == number
+            ));
+            config.record_soulbound(&mut player_progress);
+            tier.total_minted += 1;
+        }
+
+        assert!(!tier.has_supply_left());
+        assert_eq!(config.total_editions, 3);
+        assert_eq!(config.total_nfts_minted, 0);
+        assert_eq!(config.total_nfts_frozen, 2);
+        assert_eq!(player_progress.total_nfts_frozen, 2);
+    }
+}
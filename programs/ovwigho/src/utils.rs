@@ -0,0 +1,511 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token::ID as TOKEN_PROGRAM_ID,
+    token_2022::ID as TOKEN_2022_PROGRAM_ID,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use mpl_bubblegum::{hash::hash_creators, instructions::MintV2CpiBuilder, types::MetadataArgsV2};
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_asset_plugin, fetch_collection_plugin,
+    instructions::{AddCollectionPluginV1CpiBuilder, UpdateCollectionPluginV1CpiBuilder},
+    types::{Attributes, PermanentFreezeDelegate, Plugin, PluginType, UpdateAuthority},
+};
+
+use crate::{
+    error::ErrorCode, Config, Gate, MintDrop, Payment, PlayerProgress, Season, SessionToken,
+};
+
+/// Updates `plugin` on a Core collection, adding it first if the collection
+/// doesn't carry a plugin of that type yet.
+pub fn upsert_collection_plugin<'info>(
+    core_program: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    plugin: Plugin,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let exists = fetch_collection_plugin::<()>(collection, PluginType::from(&plugin)).is_ok();
+
+    if exists {
+        UpdateCollectionPluginV1CpiBuilder::new(core_program)
+            .collection(collection)
+            .payer(payer)
+            .authority(Some(authority))
+            .system_program(system_program)
+            .plugin(plugin)
+            .invoke_signed(signer_seeds)?;
+    } else {
+        AddCollectionPluginV1CpiBuilder::new(core_program)
+            .collection(collection)
+            .payer(payer)
+            .authority(Some(authority))
+            .system_program(system_program)
+            .plugin(plugin)
+            .invoke_signed(signer_seeds)?;
+    }
+
+    Ok(())
+}
+
+/// Reads an attribute from the `Attributes` plugin of a Core asset.
+pub fn asset_attribute(asset: &AccountInfo, key: &str) -> Option<String> {
+    let (_, attributes, _) =
+        fetch_asset_plugin::<Attributes>(asset, PluginType::Attributes).ok()?;
+
+    attributes
+        .attribute_list
+        .into_iter()
+        .find(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value)
+}
+
+/// Whether a Core asset is currently held by its permanent freeze delegate.
+pub fn asset_is_frozen(asset: &AccountInfo) -> bool {
+    fetch_asset_plugin::<PermanentFreezeDelegate>(asset, PluginType::PermanentFreezeDelegate)
+        .is_ok_and(|(_, freeze_delegate, _)| freeze_delegate.frozen)
+}
+
+/// Moves an asset in or out of the frozen totals. Soulbound assets are counted
+/// when crafted, so freezing, thawing and burning all go through here.
+pub fn record_freeze(config: &mut Config, player_progress: &mut PlayerProgress, frozen: bool) {
+    if frozen {
+        config.total_nfts_frozen += 1;
+        player_progress.total_nfts_frozen += 1;
+    } else {
+        config.total_nfts_frozen = config.total_nfts_frozen.saturating_sub(1);
+        player_progress.total_nfts_frozen = player_progress.total_nfts_frozen.saturating_sub(1);
+    }
+}
+
+pub fn asset_owner(asset: &AccountInfo) -> Option<Pubkey> {
+    let data = asset.try_borrow_data().ok()?;
+
+    BaseAssetV1::from_bytes(&data).ok().map(|asset| asset.owner)
+}
+
+/// Accounts needed to mint a leaf into the config's tree and cNFT collection,
+/// with the config PDA signing as tree and collection authority.
+pub struct CnftMintAccounts<'b, 'info> {
+    pub bubblegum_program: &'b AccountInfo<'info>,
+    pub tree_config: &'b AccountInfo<'info>,
+    pub leaf_owner: &'b AccountInfo<'info>,
+    pub leaf_delegate: &'b AccountInfo<'info>,
+    pub merkle_tree: &'b AccountInfo<'info>,
+    pub payer: &'b AccountInfo<'info>,
+    pub config: &'b AccountInfo<'info>,
+    pub cnft_collection: &'b AccountInfo<'info>,
+    pub mpl_core_cpi_signer: &'b AccountInfo<'info>,
+    pub log_wrapper: &'b AccountInfo<'info>,
+    pub compression_program: &'b AccountInfo<'info>,
+    pub system_program: &'b AccountInfo<'info>,
+    pub mpl_core_program: &'b AccountInfo<'info>,
+}
+
+pub fn mint_cnft_leaf(
+    accounts: &CnftMintAccounts,
+    metadata: MetadataArgsV2,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    MintV2CpiBuilder::new(accounts.bubblegum_program)
+        .tree_config(accounts.tree_config)
+        .leaf_owner(accounts.leaf_owner)
+        .leaf_delegate(Some(accounts.leaf_delegate))
+        .merkle_tree(accounts.merkle_tree)
+        .payer(accounts.payer)
+        .tree_creator_or_delegate(Some(accounts.config))
+        .core_collection(Some(accounts.cnft_collection))
+        .collection_authority(Some(accounts.config))
+        .mpl_core_cpi_signer(Some(accounts.mpl_core_cpi_signer))
+        .log_wrapper(accounts.log_wrapper)
+        .compression_program(accounts.compression_program)
+        .system_program(accounts.system_program)
+        .mpl_core_program(accounts.mpl_core_program)
+        .metadata(metadata)
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
+
+/// Transfers a lamport fee from `payer` into the config treasury.
+pub fn collect_fee<'info>(
+    payer: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    transfer(
+        CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: treasury.clone(),
+            },
+        ),
+        amount,
+    )
+}
+
+pub struct TokenPaymentAccounts<'b, 'info> {
+    pub config: &'b Account<'info, Config>,
+    pub payer: &'b AccountInfo<'info>,
+    pub payment_mint: Option<&'b InterfaceAccount<'info, Mint>>,
+    pub payer_token_account: Option<&'b InterfaceAccount<'info, TokenAccount>>,
+    pub treasury_token_account: Option<&'b InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<&'b Interface<'info, TokenInterface>>,
+}
+
+/// Transfers `amount` of the config's payment mint from the payer into the
+/// treasury token account, an ATA owned by the `config` PDA.
+pub fn collect_token_payment(accounts: &TokenPaymentAccounts, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let (
+        Some(payment_mint),
+        Some(payer_token_account),
+        Some(treasury_token_account),
+        Some(token_program),
+    ) = (
+        accounts.payment_mint,
+        accounts.payer_token_account,
+        accounts.treasury_token_account,
+        accounts.token_program,
+    )
+    else {
+        return err!(ErrorCode::PaymentAccountsMissing);
+    };
+
+    require!(
+        accounts.config.payment_mint == Some(payment_mint.key()),
+        ErrorCode::PaymentMintMismatch
+    );
+    require_keys_eq!(
+        treasury_token_account.key(),
+        get_associated_token_address_with_program_id(
+            &accounts.config.key(),
+            &payment_mint.key(),
+            &token_program.key(),
+        ),
+        ErrorCode::InvalidTreasuryTokenAccount
+    );
+
+    transfer_checked(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: payer_token_account.to_account_info(),
+                mint: payment_mint.to_account_info(),
+                to: treasury_token_account.to_account_info(),
+                authority: accounts.payer.clone(),
+            },
+        ),
+        amount,
+        payment_mint.decimals,
+    )
+}
+
+/// Charges `payment` to the payer, into the treasury PDA or the treasury
+/// token account.
+pub fn collect_payment<'info>(
+    accounts: &TokenPaymentAccounts<'_, 'info>,
+    treasury: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    payment: Payment,
+) -> Result<()> {
+    match payment {
+        Payment::Lamports(amount) => collect_fee(accounts.payer, treasury, system_program, amount),
+        Payment::Tokens(amount) => collect_token_payment(accounts, amount),
+    }
+}
+
+pub fn signer_keys(accounts: &[AccountInfo]) -> Vec<Pubkey> {
+    accounts
+        .iter()
+        .filter(|account| account.is_signer)
+        .map(|account| account.key())
+        .collect()
+}
+
+/// Creator hash of a leaf minted by this config, through `mint_drop` if given.
+pub fn leaf_creator_hash(
+    config: &Config,
+    config_key: Pubkey,
+    mint_drop: Option<(Pubkey, &MintDrop)>,
+) -> Result<[u8; 32]> {
+    let mut metadata =
+        config.leaf_metadata(config_key, String::new(), String::new(), String::new());
+    if let Some((drop, mint_drop)) = mint_drop {
+        mint_drop.stamp(drop, &mut metadata)?;
+    }
+
+    Ok(hash_creators(&metadata.creators))
+}
+
+/// Leaves are delegated to the config PDA when asked to, or when minted through
+/// a session, so the program can burn them for an authorized session later.
+/// The session key itself never becomes a delegate, as that would outlive the
+/// session.
+pub fn delegates_to_config(delegate_to_config: bool, player: &Pubkey, signer: &Pubkey) -> bool {
+    delegate_to_config || player != signer
+}
+
+/// Checks that `signer` may act for `player`: either it is the player, or it
+/// holds an unexpired session token covering `scope`.
+pub fn authorize_player(
+    player: &Pubkey,
+    signer: &Pubkey,
+    session_token: Option<&Account<SessionToken>>,
+    scope: u8,
+) -> Result<()> {
+    if player == signer {
+        return Ok(());
+    }
+
+    let Some(session_token) = session_token else {
+        return err!(ErrorCode::UnauthorizedSigner);
+    };
+
+    require_keys_eq!(session_token.player, *player, ErrorCode::UnauthorizedSigner);
+    require_keys_eq!(
+        session_token.session_key,
+        *signer,
+        ErrorCode::UnauthorizedSigner
+    );
+    require!(
+        session_token.allows(scope, Clock::get()?.unix_timestamp),
+        ErrorCode::UnauthorizedSigner
+    );
+
+    Ok(())
+}
+
+/// Returns the config's active season, checking that the passed account is
+/// that season and that the clock is inside its window. `None` when no
+/// season is running.
+pub fn active_season<'a>(
+    config: &Config,
+    season: Option<&'a Account<Season>>,
+) -> Result<Option<&'a Season>> {
+    let Some(active_season) = config.active_season else {
+        return Ok(None);
+    };
+
+    let Some(season) = season else {
+        return err!(ErrorCode::SeasonAccountMissing);
+    };
+
+    require!(season.id == active_season, ErrorCode::SeasonNotActive);
+    require!(
+        season.is_open(Clock::get()?.unix_timestamp),
+        ErrorCode::SeasonNotActive
+    );
+
+    Ok(Some(season))
+}
+
+/// Checks that `player` meets `gate` with the Core asset or token account
+/// passed as `gate_account`. No gate means no requirement.
+pub fn check_gate(
+    player: &Pubkey,
+    gate: Option<&Gate>,
+    gate_account: Option<&AccountInfo>,
+) -> Result<()> {
+    let Some(gate) = gate else {
+        return Ok(());
+    };
+
+    let Some(gate_account) = gate_account else {
+        return err!(ErrorCode::GateAccountMissing);
+    };
+
+    let data = gate_account.try_borrow_data()?;
+
+    let meets_gate = match gate {
+        Gate::CoreCollection { collection } => {
+            *gate_account.owner == mpl_core::ID
+                && BaseAssetV1::from_bytes(&data).is_ok_and(|asset| {
+                    asset.owner == *player
+                        && asset.update_authority == UpdateAuthority::Collection(*collection)
+                })
+        }
+        Gate::TokenBalance { mint, min_amount } => {
+            (*gate_account.owner == TOKEN_PROGRAM_ID
+                || *gate_account.owner == TOKEN_2022_PROGRAM_ID)
+                && TokenAccount::try_deserialize(&mut &data[..]).is_ok_and(|token_account| {
+                    token_account.owner == *player
+                        && token_account.mint == *mint
+                        && token_account.amount >= *min_amount
+                })
+        }
+    };
+
+    require!(meets_gate, ErrorCode::GateNotMet);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        state::config::tests::test_config, SESSION_SCOPE_BURN_CNFT, SESSION_SCOPE_MINT_CNFT,
+    };
+
+    #[test]
+    fn session_mints_delegate_to_the_config_not_the_session_key() {
+        let player = Pubkey::new_unique();
+        let session_key = Pubkey::new_unique();
+
+        assert!(!delegates_to_config(false, &player, &player));
+        assert!(delegates_to_config(true, &player, &player));
+        assert!(delegates_to_config(false, &player, &session_key));
+
+        let session = SessionToken {
+            player,
+            session_key,
+            expires_at: 100,
+            scope: SESSION_SCOPE_BURN_CNFT,
+            bump: 255,
+        };
+        assert!(session.allows(SESSION_SCOPE_BURN_CNFT, 99));
+        assert!(!session.allows(SESSION_SCOPE_BURN_CNFT, 100));
+        assert!(!session.allows(SESSION_SCOPE_MINT_CNFT, 99));
+    }
+
+    #[test]
+    fn session_mints_are_charged_to_an_approved_payer() {
+        let mut config = test_config(Pubkey::new_unique());
+        let (player, session_key, sponsor) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        config.sponsors = vec![sponsor];
+
+        // The fee and token payment come from `payer`, which a session may
+        // only be paired with when it is the session key itself or a sponsor.
+        assert!(config.can_pay_for(&sponsor, &session_key));
+        assert!(config.can_pay_for(&session_key, &session_key));
+        assert!(!config.can_pay_for(&player, &session_key));
+        assert!(delegates_to_config(false, &player, &session_key));
+    }
+
+    #[test]
+    fn soulbound_crafts_and_thaws_count_the_same_frozen_assets() {
+        let mut config = test_config(Pubkey::new_unique());
+        let mut player_progress = PlayerProgress::default();
+        config.soulbound = true;
+
+        config.record_craft(&mut player_progress);
+        assert_eq!(config.total_nfts_frozen, 1);
+        assert_eq!(player_progress.total_nfts_frozen, 1);
+
+        record_freeze(&mut config, &mut player_progress, false);
+        assert_eq!(config.total_nfts_frozen, 0);
+        assert_eq!(player_progress.total_nfts_frozen, 0);
+
+        record_freeze(&mut config, &mut player_progress, true);
+        record_freeze(&mut config, &mut player_progress, false);
+        record_freeze(&mut config, &mut player_progress, false);
+        assert_eq!(config.total_nfts_frozen, 0);
+
+        assert_eq!(config.total_nfts_minted, 1);
+        assert_eq!(config.total_editions, 1);
+    }
+
+    #[test]
+    fn burns_of_drop_leaves_hash_the_drop_as_a_creator() {
+        let config_key = Pubkey::new_unique();
+        let config = test_config(config_key);
+        let drop = Pubkey::new_unique();
+        let mint_drop = MintDrop {
+            config: config_key,
+            id: 1,
+            mint_start: None,
+            mint_end: None,
+            max_supply: None,
+            total_minted: 0,
+            allowlist_root: None,
+            bump: 255,
+        };
+
+        let plain = leaf_creator_hash(&config, config_key, None).unwrap();
+        assert_eq!(plain, hash_creators(&config.leaf_creators(config_key)));
+
+        let mut creators = config.leaf_creators(config_key);
+        creators.push(mpl_bubblegum::types::Creator {
+            address: drop,
+            verified: false,
+            share: 0,
+        });
+        let stamped = leaf_creator_hash(&config, config_key, Some((drop, &mint_drop))).unwrap();
+        assert_eq!(stamped, hash_creators(&creators));
+        assert_ne!(stamped, plain);
+    }
+
+    #[test]
+    fn token_gates_check_owner_mint_and_balance() {
+        use anchor_lang::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::{Account as SplAccount, AccountState};
+
+        let player = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let gate = Gate::TokenBalance {
+            mint,
+            min_amount: 5,
+        };
+
+        let gate_account = |owner: Pubkey, mint: Pubkey, amount: u64| {
+            let mut data = vec![0; SplAccount::LEN];
+            SplAccount {
+                mint,
+                owner,
+                amount,
+                state: AccountState::Initialized,
+                ..Default::default()
+            }
+            .pack_into_slice(&mut data);
+            data
+        };
+        let check = |data: &mut Vec<u8>| {
+            let key = Pubkey::new_unique();
+            let mut lamports = 0;
+            let info = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                data,
+                &TOKEN_PROGRAM_ID,
+                false,
+                0,
+            );
+            check_gate(&player, Some(&gate), Some(&info))
+        };
+
+        assert!(check_gate(&player, None, None).is_ok());
+        assert_eq!(
+            check_gate(&player, Some(&gate), None).unwrap_err(),
+            ErrorCode::GateAccountMissing.into()
+        );
+
+        assert!(check(&mut gate_account(player, mint, 5)).is_ok());
+        for mut data in [
+            gate_account(player, mint, 4),
+            gate_account(Pubkey::new_unique(), mint, 5),
+            gate_account(player, Pubkey::new_unique(), 5),
+        ] {
+            assert_eq!(check(&mut data).unwrap_err(), ErrorCode::GateNotMet.into());
+        }
+    }
+}
@@ -137,10 +137,15 @@ describe("ovwigho", () => {
     //         {
     //           name: "test cNFT",
     //           uri: "https://raw.githubusercontent.com/amxrac/cmd-token/refs/heads/main/cnft%20metadata.json",
+    //           royalties: null,
+    //           isMutable: false,
     //         },
     //         {
     //           name: "test NFT",
     //           uri: "https://raw.githubusercontent.com/amxrac/cmd-token/refs/heads/main/nft%20metadata.json",
+    //           maxSupply: null,
+    //           royalties: null,
+    //           moderation: false,
     //         }
     //       )
     //       .accounts({
@@ -406,11 +411,11 @@ describe("ovwigho", () => {
     //       .burnCnft(
     //         Array.from(assetWithProof.root),
     //         Array.from(assetWithProof.dataHash),
-    //         Array.from(assetWithProof.creatorHash),
     //         new BN(assetWithProof.nonce),
     //         assetWithProof.index,
     //         Array.from(assetWithProof.asset_data_hash),
-    //         assetWithProof.flags
+    //         assetWithProof.flags,
+    //         null
     //       )
     //       .accounts({
     //         player: playerOne.publicKey,
@@ -479,11 +484,11 @@ describe("ovwigho", () => {
     //       .burnCnft(
     //         Array.from(assetWithProof.root),
     //         Array.from(assetWithProof.dataHash),
-    //         Array.from(assetWithProof.creatorHash),
     //         new BN(assetWithProof.nonce),
     //         assetWithProof.index,
     //         Array.from(assetWithProof.asset_data_hash),
-    //         assetWithProof.flags
+    //         assetWithProof.flags,
+    //         null
     //       )
     //       .accounts({
     //         player: playerTwo.publicKey,
@@ -521,18 +526,39 @@ describe("ovwigho", () => {
     it("mints an NFT", async () => {
       let configAccount = await program.account.config.fetch(configPda);
       let initializedNftCollection = configAccount.nftCollection;
+      const treasuryPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury"), configPda.toBuffer()],
+        program.programId
+      )[0];
+      const craftReceiptPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("receipt"), asset.publicKey.toBuffer()],
+        program.programId
+      )[0];
       try {
         const sig = await program.methods
           .mintNft(
             "sample nft",
             "https://raw.githubusercontent.com/amxrac/cmd-token/refs/heads/main/nft%20metadata.json"
           )
-          .accounts({
+          .accountsPartial({
             player: playerOne.publicKey,
+            signer: playerOne.publicKey,
+            sessionToken: null,
+            payer: playerOne.publicKey,
             authority: wallet.publicKey,
+            config: configPda,
+            season: null,
+            treasury: treasuryPda,
+            playerProgress: playerOneProgressPda,
             nftCollection: initializedNftCollection,
             asset: asset.publicKey,
+            craftReceipt: craftReceiptPda,
             coreProgram: MPL_CORE_PROGRAM_ID,
+            paymentMint: null,
+            payerTokenAccount: null,
+            treasuryTokenAccount: null,
+            tokenProgram: null,
+            gateAccount: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([playerOne, asset])